@@ -1,11 +1,28 @@
 use criterion::{criterion_group, criterion_main, Criterion};
 
+fn ch_04_2(c: &mut Criterion) {
+  let input = std::fs::read_to_string("../input/2023/04.txt").unwrap();
+  let challenge = "2023/4/2".parse().unwrap();
+  c.bench_function("2023/4/2", |b| {
+    b.iter(|| challenges::run(&input, challenge).unwrap())
+  });
+}
+
 fn ch_05_2(c: &mut Criterion) {
   let input = std::fs::read_to_string("../input/2023/05.txt").unwrap();
+  let challenge = "2023/5/1".parse().unwrap();
   c.bench_function("2023/5/2", |b| {
-    b.iter(|| challenges::run(&input, "2023/5/1"))
+    b.iter(|| challenges::run(&input, challenge).unwrap())
+  });
+}
+
+fn ch_08_2(c: &mut Criterion) {
+  let input = std::fs::read_to_string("../input/2023/08.txt").unwrap();
+  let challenge = "2023/8/2".parse().unwrap();
+  c.bench_function("2023/8/2", |b| {
+    b.iter(|| challenges::run(&input, challenge).unwrap())
   });
 }
 
-criterion_group!(benches, ch_05_2);
+criterion_group!(benches, ch_04_2, ch_05_2, ch_08_2);
 criterion_main!(benches);
@@ -1,8 +1,9 @@
 use self::parser::run_match;
+use crate::error::SolveError;
 
 mod parser;
 
-pub fn part_1(input: &str) -> i64 {
+pub fn part_1(input: &str) -> Result<i64, SolveError> {
   let mut sum: i64 = 0;
   for line in input.lines() {
     sum +=
@@ -18,11 +19,13 @@ pub fn part_1(input: &str) -> i64 {
       .parse::<i64>()
       .unwrap();
   }
-  sum
+  Ok(sum)
 }
 
-pub fn part_2(input: &str) -> i64 {
-  input
-    .lines()
-    .fold(0, |acc, line| acc + run_match(line).unwrap())
+pub fn part_2(input: &str) -> Result<i64, SolveError> {
+  Ok(
+    input
+      .lines()
+      .fold(0, |acc, line| acc + run_match(line).unwrap()),
+  )
 }
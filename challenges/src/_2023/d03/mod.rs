@@ -1,47 +1,50 @@
-use std::collections::HashMap;
-
-const fn linear_index(row: usize, col: usize, width: usize) -> usize {
-  row * width + col
-}
+use crate::{
+  error::SolveError,
+  grid::{Grid, Point},
+};
 
 type Num = u32;
-type NumHashMap = HashMap<usize, Num>;
+type NumGrid = Grid<Option<Num>>;
 
-fn hash_numbers(lines: &Vec<&str>) -> NumHashMap {
-  let mut hash = HashMap::new();
-  for (i, line) in lines.iter().enumerate() {
+fn grid_numbers(schematic: &Grid<char>) -> NumGrid {
+  let mut numbers = schematic.map(|_| None);
+  for (y, row) in schematic.rows().enumerate() {
     let mut start: Option<usize> = None;
     let mut end = 0;
 
     fn token_end(
       maybe_start: Option<usize>,
       end: usize,
-      line: &str,
-      hash: &mut NumHashMap,
-      row: usize,
+      row: &[char],
+      numbers: &mut NumGrid,
+      y: usize,
     ) {
       if let Some(start) = maybe_start {
-        let value = line[start..end].parse::<Num>().unwrap();
-        for col in start..end {
-          hash.insert(linear_index(row, col, line.len()), value);
+        let value = row[start..end]
+          .iter()
+          .collect::<String>()
+          .parse::<Num>()
+          .unwrap();
+        for x in start..end {
+          numbers[Point::new(x as isize, y as isize)] = Some(value);
         }
       }
     }
 
-    for (j, c) in line.chars().enumerate() {
+    for (x, c) in row.iter().enumerate() {
       if c.is_ascii_digit() {
         if start.is_none() {
-          start = Some(j);
+          start = Some(x);
         }
-        end = j;
+        end = x;
       } else {
-        token_end(start, end + 1, line, &mut hash, i);
+        token_end(start, end + 1, row, &mut numbers, y);
         start = None;
       }
     }
-    token_end(start, end + 1, line, &mut hash, i);
+    token_end(start, end + 1, row, &mut numbers, y);
   }
-  hash
+  numbers
 }
 
 fn is_part_symbol(c: &char) -> bool {
@@ -75,62 +78,53 @@ impl Part {
   }
 }
 
-fn extract_parts(lines: &Vec<&str>, hash: &NumHashMap) -> Vec<Part> {
+fn extract_parts(schematic: &Grid<char>, numbers: &NumGrid) -> Vec<Part> {
   let mut parts: Vec<Part> = Vec::new();
-  for (row, line) in lines.iter().enumerate() {
-    for (col, c) in line.chars().enumerate() {
-      if !is_part_symbol(&c) {
-        continue;
-      }
-      let mut part = Part::empty(c);
-      let width = line.len();
-      let irow = row as isize;
-      let icol = col as isize;
-      for i in [irow - 1, irow, irow + 1].into_iter() {
-        // Check middle column first
-        for j in [icol, icol - 1, icol + 1].into_iter() {
-          // Skip if out of bounds
-          if i < 0 || j < 0 || i >= lines.len() as isize || j >= width as isize
-          {
-            continue;
-          }
-          // Skip if we are at the symbol itself
-          if i == irow && j == icol {
-            continue;
-          }
-          if let Some(value) =
-            hash.get(&linear_index(i as usize, j as usize, width))
-          {
-            part.add_value(*value);
-            // If there is a number in the middle, left and right are not possible
-            if j == icol {
-              break;
-            }
+  for (p, c) in schematic.iter_with_points() {
+    if !is_part_symbol(c) {
+      continue;
+    }
+    let mut part = Part::empty(*c);
+    for dy in [-1, 0, 1] {
+      // Check middle column first
+      for dx in [0, -1, 1] {
+        // Skip if we are at the symbol itself
+        if dy == 0 && dx == 0 {
+          continue;
+        }
+        // Out of bounds cells hold no number
+        if let Some(Some(value)) = numbers.get(Point::new(p.x + dx, p.y + dy)) {
+          part.add_value(*value);
+          // If there is a number in the middle, left and right are not possible
+          if dx == 0 {
+            break;
           }
         }
       }
-      if !part.values.is_empty() {
-        parts.push(part);
-      }
+    }
+    if !part.values.is_empty() {
+      parts.push(part);
     }
   }
   parts
 }
 
-pub fn part_1(input: &str) -> i64 {
-  let lines: Vec<&str> = input.lines().collect();
-  let hash = hash_numbers(&lines);
-  let parts = extract_parts(&lines, &hash);
-  parts.iter().map(|part| part.sum() as i64).sum()
+pub fn part_1(input: &str) -> Result<i64, SolveError> {
+  let schematic = input.parse::<Grid<char>>()?;
+  let numbers = grid_numbers(&schematic);
+  let parts = extract_parts(&schematic, &numbers);
+  Ok(parts.iter().map(|part| part.sum() as i64).sum())
 }
 
-pub fn part_2(input: &str) -> i64 {
-  let lines: Vec<&str> = input.lines().collect();
-  let hash = hash_numbers(&lines);
-  let parts = extract_parts(&lines, &hash);
-  parts
-    .iter()
-    .filter(|part| part.symbol == '*' && part.values.len() == 2)
-    .map(|part| part.product() as i64)
-    .sum()
+pub fn part_2(input: &str) -> Result<i64, SolveError> {
+  let schematic = input.parse::<Grid<char>>()?;
+  let numbers = grid_numbers(&schematic);
+  let parts = extract_parts(&schematic, &numbers);
+  Ok(
+    parts
+      .iter()
+      .filter(|part| part.symbol == '*' && part.values.len() == 2)
+      .map(|part| part.product() as i64)
+      .sum(),
+  )
 }
@@ -1,17 +1,31 @@
-use std::{collections::HashSet, fmt::Debug};
+use std::fmt::Debug;
+
+use nom::{
+  bytes::complete::tag,
+  character::complete::space1,
+  combinator::map_opt,
+  error::context,
+  sequence::{pair, preceded, separated_pair},
+};
+
+use crate::{
+  error::SolveError,
+  parser::{key_values, lines, numbers, parse_all, unsigned, ws_line, PResult},
+};
 
 type Num = u32;
 
-fn is_not_empty_str(s: &&str) -> bool {
-  !s.is_empty()
-}
+/// Set of numbers below 128.
+type NumberSet = u128;
 
-fn parse_set(input: &str) -> HashSet<Num> {
-  input
-    .split(" ")
-    .filter(is_not_empty_str)
-    .map(|s| s.parse::<Num>().expect("Invalid number"))
-    .collect::<HashSet<Num>>()
+fn number_set(input: &str) -> PResult<'_, NumberSet> {
+  context(
+    "number below 128",
+    map_opt(numbers::<u32, _>, |ns| {
+      ns.into_iter()
+        .try_fold(0, |set, n| Some(set | 1u128.checked_shl(n)?))
+    }),
+  )(input)
 }
 
 //#[derive(Debug)]
@@ -27,34 +41,16 @@ impl Debug for Card {
 }
 
 impl Card {
-  fn parse(input: &str) -> Self {
-    let [game, sets] = *input.split(":").collect::<Box<[_]>>() else {
-      panic!("Invalid input")
-    };
-    let id = game
-      .split(" ")
-      .filter(is_not_empty_str)
-      .nth(1)
-      .expect("Invalid input")
-      .parse::<usize>()
-      .expect("Invalid number");
-    let [winning_str, available_str] = *sets.split("|").collect::<Box<[_]>>()
-    else {
-      panic!("Invalid input")
-    };
-    let winning = parse_set(winning_str);
-    let available = parse_set(available_str);
-    let matches = winning.intersection(&available).count() as Num;
-    Self { id, matches }
-  }
-
-  fn slice_cards<'a>(&self, cards: &'a [Card]) -> &'a [Card] {
-    let start = self.id;
-    if start >= cards.len() {
-      return &[];
-    }
-    let end = std::cmp::min(cards.len(), start + self.matches as usize);
-    &cards[start..end]
+  fn parse(input: &str) -> PResult<'_, Self> {
+    let (input, (id, (winning, available))) = context(
+      "card",
+      key_values(
+        preceded(pair(tag("Card"), space1), unsigned),
+        separated_pair(number_set, ws_line(tag("|")), number_set),
+      ),
+    )(input)?;
+    let matches = (winning & available).count_ones();
+    Ok((input, Self { id, matches }))
   }
 
   fn score(&self) -> Num {
@@ -65,25 +61,47 @@ impl Card {
   }
 }
 
+/// Each card wins one copy of the next cards for every copy of itself, so
+/// the copies are counted in one pass, with the pending wins kept as
+/// differences between consecutive cards.
 fn count_copies(cards: &[Card]) -> i64 {
-  fn _count(subset: &[Card], cards: &[Card]) -> i64 {
-    let mut count = 0;
-    for card in subset {
-      count += 1 + _count(card.slice_cards(cards), cards);
-    }
-    count
+  let mut won = vec![0i64; cards.len() + 1];
+  let mut pending = 0;
+  let mut total = 0;
+  for (i, card) in cards.iter().enumerate() {
+    pending += won[i];
+    let copies = 1 + pending;
+    total += copies;
+    let end = cards.len().min(i + 1 + card.matches as usize);
+    won[i + 1] += copies;
+    won[end] -= copies;
   }
-  _count(cards, cards)
+  total
+}
+
+pub fn part_1(input: &str) -> Result<i64, SolveError> {
+  let cards = parse_all(lines(Card::parse), input)?;
+  Ok(cards.iter().map(Card::score).sum::<Num>() as i64)
 }
 
-pub fn part_1(input: &str) -> i64 {
-  input
-    .lines()
-    .map(|line| Card::parse(line).score())
-    .sum::<Num>() as i64
+pub fn part_2(input: &str) -> Result<i64, SolveError> {
+  let cards = parse_all(lines(Card::parse), input)?;
+  Ok(count_copies(cards.as_slice()))
 }
 
-pub fn part_2(input: &str) -> i64 {
-  let cards = input.lines().map(Card::parse).collect::<Vec<Card>>();
-  count_copies(cards.as_slice())
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn rejects_numbers_from_128() {
+    assert_eq!(part_1("Card 1: 127 | 127"), Ok(1));
+    match part_1("Card 1: 1 128 | 3") {
+      Err(SolveError::Parse(e)) => {
+        assert_eq!(e.expected, "number below 128");
+        assert_eq!((e.line, e.column), (1, 9));
+      }
+      other => panic!("expected a parse error, got {:?}", other),
+    }
+  }
 }
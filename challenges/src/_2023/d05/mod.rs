@@ -1,14 +1,14 @@
 use self::parser::{parser_list, parser_ranges};
+use crate::error::SolveError;
 
 mod parser;
-mod range;
+#[cfg(test)]
+mod tests;
 
-pub fn part_1(input: &str) -> i64 {
-  let (_, almanac) = parser_list(input).unwrap();
-  almanac.map_ranges_lowest()
+pub fn part_1(input: &str) -> Result<i64, SolveError> {
+  parser_list(input)?.map_ranges_lowest()
 }
 
-pub fn part_2(input: &str) -> i64 {
-  let (_, almanac) = parser_ranges(input).unwrap();
-  almanac.map_ranges_lowest()
+pub fn part_2(input: &str) -> Result<i64, SolveError> {
+  parser_ranges(input)?.map_ranges_lowest()
 }
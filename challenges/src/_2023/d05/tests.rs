@@ -0,0 +1,85 @@
+use super::parser::{parser_list, parser_ranges};
+use crate::{
+  differential::{check, Rng},
+  error::SolveError,
+};
+
+/// Random almanac text whose mapping lines may overlap, touch or leave gaps,
+/// with small numbers so the per-seed path stays cheap.
+fn almanac(rng: &mut Rng) -> String {
+  let mut text = String::from("seeds:");
+  for _ in 0..rng.range(1..4) {
+    text += &format!(" {} {}", rng.range(0..100), rng.range(1..30));
+  }
+  text += "\n\n";
+  for map in 0..rng.range(1..5) {
+    text += &format!("a{}-to-b{} map:\n", map, map);
+    let mut src = rng.range(0..40);
+    for _ in 0..rng.range(1..5) {
+      let len = rng.range(1..30);
+      text += &format!("{} {} {}\n", rng.range(0..150), src, len);
+      src = match rng.range(0..3) {
+        0 => src + len,
+        1 => src + len + rng.range(1..20),
+        _ => (src + rng.range(-20..len)).max(0),
+      };
+    }
+    text += "\n";
+  }
+  text
+}
+
+#[test]
+fn example() {
+  let input = std::fs::read_to_string("../input/2023/05_a.txt").unwrap();
+  assert_eq!(super::part_1(&input), Ok(35));
+  assert_eq!(super::part_2(&input), Ok(46));
+}
+
+#[test]
+fn ranges_match_seeds() {
+  check(
+    5,
+    2000,
+    almanac,
+    |text| parser_ranges(text).unwrap().map_ranges_lowest(),
+    |text| parser_ranges(text).unwrap().map_seeds_lowest(),
+  );
+}
+
+#[test]
+fn seed_list_ranges_match_seeds() {
+  check(
+    7,
+    2000,
+    almanac,
+    |text| parser_list(text).unwrap().map_ranges_lowest(),
+    |text| parser_list(text).unwrap().map_seeds_lowest(),
+  );
+}
+
+#[test]
+fn seed_ranges_come_in_pairs() {
+  let maps = "\n\na-to-b map:\n1 2 3\n";
+  match super::part_2(&format!("seeds: 1 2 3{}", maps)) {
+    Err(SolveError::Parse(e)) => {
+      assert_eq!((e.line, e.column), (1, 13));
+      assert_eq!(e.expected, "space in seed range");
+    }
+    other => panic!("expected a parse error, got {:?}", other),
+  }
+  assert!(super::part_1(&format!("seeds: 1 -2{}", maps)).is_err());
+  assert_eq!(super::part_1(&format!("seeds: 1 2 3{}", maps)), Ok(1));
+}
+
+#[test]
+fn empty_seed_ranges() {
+  let input = "seeds: 79 0 14 0\n\na-to-b map:\n1 2 3\n";
+  let empty = Err(SolveError::NoSolution("every seed range is empty".into()));
+  assert_eq!(super::part_2(input), empty);
+  assert_eq!(parser_ranges(input).unwrap().map_seeds_lowest(), empty);
+  assert_eq!(
+    super::part_2("seeds: 79 0 14 1\n\na-to-b map:\n1 2 3\n"),
+    Ok(14)
+  );
+}
@@ -1,39 +1,50 @@
-mod hand;
-mod part_1;
-mod part_2;
+mod rules;
+#[cfg(test)]
+mod tests;
 
-use hand::Hand;
-use std::collections::HashMap;
+use nom::{
+  bytes::complete::is_not,
+  character::complete::space1,
+  combinator::map_opt,
+  error::context,
+  sequence::{pair, preceded},
+};
+use rules::{RuleSet, JOKERS, STANDARD};
 
-pub fn part_1(input: &str) -> i64 {
-  let lines = input.lines();
-  let mut cards = lines
-    .map(|l| {
-      let (a, b) = l.split_at(5);
-      (Hand::parse_v1(a), b.trim().parse::<i64>().unwrap())
-    })
-    .collect::<Vec<_>>();
-  cards.sort_by(|a, b| a.0.cmp(&b.0));
-  cards
-    .iter()
-    .enumerate()
-    .map(|(i, c)| ((i + 1) as i64) * c.1)
-    .sum()
+use crate::{
+  error::SolveError,
+  parser::{lines, parse_all, unsigned, PResult},
+};
+
+fn hand_bid<'a>(
+  rules: &'a RuleSet,
+) -> impl FnMut(&'a str) -> PResult<'a, (u128, i64)> {
+  context(
+    "hand",
+    pair(
+      // The whole word, so a hand of the wrong size is reported as such
+      context("5 cards", map_opt(is_not(" \r\n"), |hand| rules.key(hand))),
+      context("bid", preceded(space1, unsigned)),
+    ),
+  )
+}
+
+fn total_winnings(rules: &RuleSet, input: &str) -> Result<i64, SolveError> {
+  let mut hands = parse_all(lines(hand_bid(rules)), input)?;
+  hands.sort_unstable();
+  Ok(
+    hands
+      .iter()
+      .enumerate()
+      .map(|(i, (_, bid))| (i + 1) as i64 * bid)
+      .sum(),
+  )
+}
+
+pub fn part_1(input: &str) -> Result<i64, SolveError> {
+  total_winnings(&STANDARD, input)
 }
 
-pub fn part_2(input: &str) -> i64 {
-  let lines = input.lines();
-  let mut cards = lines
-    .map(|l| {
-      let (a, b) = l.split_at(5);
-      (Hand::parse_v2(a), b.trim().parse::<i64>().unwrap())
-    })
-    .collect::<Vec<_>>();
-  //println!("{:?}", cards);
-  cards.sort_by(|a, b| a.0.cmp(&b.0));
-  cards
-    .iter()
-    .enumerate()
-    .map(|(i, c)| ((i + 1) as i64) * c.1)
-    .sum()
+pub fn part_2(input: &str) -> Result<i64, SolveError> {
+  total_winnings(&JOKERS, input)
 }
@@ -0,0 +1,191 @@
+/// How hands of a Camel Cards style game are read and ranked. Cards are
+/// listed from weakest to strongest; wildcards count as whichever card makes
+/// the best hand type, but keep their place in that order to break ties.
+pub struct RuleSet {
+  order: &'static str,
+  wildcards: &'static str,
+  hand_size: usize,
+}
+
+pub const STANDARD: RuleSet = RuleSet::new("23456789TJQKA", "", 5);
+pub const JOKERS: RuleSet = RuleSet::new("J23456789TQKA", "J", 5);
+
+impl RuleSet {
+  pub const fn new(
+    order: &'static str,
+    wildcards: &'static str,
+    hand_size: usize,
+  ) -> Self {
+    assert!(hand_size > 0, "A hand has at least one card");
+    RuleSet {
+      order,
+      wildcards,
+      hand_size,
+    }
+  }
+
+  pub fn hand_size(&self) -> usize {
+    self.hand_size
+  }
+
+  /// Place of the card in the order, `None` if it is not in the game.
+  pub fn strength(&self, card: char) -> Option<u8> {
+    self.order.chars().position(|c| c == card).map(|s| s as u8)
+  }
+
+  pub fn is_wild(&self, card: char) -> bool {
+    self.wildcards.contains(card)
+  }
+
+  /// Sizes of the groups of equal cards, largest first, with the wildcards
+  /// joining the largest group. Types compare like their shapes.
+  pub fn shape(&self, hand: &str) -> Vec<u8> {
+    let mut groups: Vec<(char, u8)> = Vec::new();
+    let mut wild = 0;
+    for card in hand.chars() {
+      if self.is_wild(card) {
+        wild += 1;
+      } else if let Some(group) = groups.iter_mut().find(|g| g.0 == card) {
+        group.1 += 1;
+      } else {
+        groups.push((card, 1));
+      }
+    }
+    let mut shape: Vec<u8> = groups.into_iter().map(|g| g.1).collect();
+    shape.sort_unstable_by(|a, b| b.cmp(a));
+    match shape.first_mut() {
+      Some(largest) => *largest += wild,
+      None => shape.push(wild),
+    }
+    shape
+  }
+
+  /// Sort key of the hand, its shape then its cards as digits. `None` for a
+  /// hand of the wrong size, with unknown cards or too large for the key.
+  pub fn key(&self, hand: &str) -> Option<u128> {
+    let cards = hand
+      .chars()
+      .map(|c| self.strength(c))
+      .collect::<Option<Vec<u8>>>()?;
+    if cards.len() != self.hand_size {
+      return None;
+    }
+    let shape = self.shape(hand);
+    let groups = shape.into_iter().chain(std::iter::repeat(0));
+    let key = pack(0, groups.take(self.hand_size), self.hand_size + 1)?;
+    pack(key, cards, self.order.chars().count())
+  }
+}
+
+/// Appends the digits to the key, `None` on overflow.
+fn pack(
+  key: u128,
+  digits: impl IntoIterator<Item = u8>,
+  radix: usize,
+) -> Option<u128> {
+  digits.into_iter().try_fold(key, |key, d| {
+    key.checked_mul(radix as u128)?.checked_add(d as u128)
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// Best shape over every way of replacing the wildcards with other cards.
+  /// The shape ignores card order, so each multiset of replacements is only
+  /// tried once, in non-decreasing order.
+  fn brute_force_shape(rules: &RuleSet, hand: &str) -> Vec<u8> {
+    let cards: Vec<char> =
+      rules.order.chars().filter(|&c| !rules.is_wild(c)).collect();
+    let mut counts = vec![0; cards.len()];
+    let mut wild = 0;
+    for card in hand.chars() {
+      match cards.iter().position(|&c| c == card) {
+        Some(i) => counts[i] += 1,
+        None => wild += 1,
+      }
+    }
+    if cards.is_empty() {
+      return vec![wild];
+    }
+    fn best(counts: &mut [u8], from: usize, wild: u8) -> Vec<u8> {
+      if wild == 0 {
+        let mut shape: Vec<u8> =
+          counts.iter().copied().filter(|&n| n > 0).collect();
+        shape.sort_unstable_by(|a, b| b.cmp(a));
+        return shape;
+      }
+      (from..counts.len())
+        .map(|i| {
+          counts[i] += 1;
+          let shape = best(counts, i, wild - 1);
+          counts[i] -= 1;
+          shape
+        })
+        .max()
+        .unwrap()
+    }
+    best(&mut counts, 0, wild)
+  }
+
+  /// Every hand of `size` cards from `order`.
+  fn all_hands(order: &str, size: usize) -> Vec<String> {
+    (0..size).fold(vec![String::new()], |hands, _| {
+      hands
+        .iter()
+        .flat_map(|h| order.chars().map(move |c| format!("{}{}", h, c)))
+        .collect()
+    })
+  }
+
+  #[test]
+  fn shapes() {
+    assert_eq!(STANDARD.shape("32T3K"), [2, 1, 1, 1]);
+    assert_eq!(STANDARD.shape("KTJJT"), [2, 2, 1]);
+    assert_eq!(JOKERS.shape("KTJJT"), [4, 1]);
+    assert_eq!(JOKERS.shape("JJJJJ"), [5]);
+    assert_eq!(JOKERS.shape("QQQJA"), [4, 1]);
+  }
+
+  #[test]
+  fn ranks_types_then_cards() {
+    let ordered = ["23456", "22345", "22334", "22234", "22233", "22223"];
+    let keys: Vec<_> = ordered.iter().map(|h| STANDARD.key(h)).collect();
+    assert!(keys.windows(2).all(|w| w[0] < w[1]), "{:?}", keys);
+    assert!(STANDARD.key("KK677") > STANDARD.key("KTJJT"));
+    // Jokers make the hand stronger but are the weakest tie breaker
+    assert!(JOKERS.key("KTJJT") > JOKERS.key("QQQJA"));
+    assert!(JOKERS.key("JKKK2") < JOKERS.key("QQQQ2"));
+  }
+
+  #[test]
+  fn joker_shapes_match_brute_force() {
+    for hand in all_hands(JOKERS.order, 5) {
+      let expected = brute_force_shape(&JOKERS, &hand);
+      assert_eq!(JOKERS.shape(&hand), expected, "{}", hand);
+    }
+  }
+
+  #[test]
+  fn several_wildcards_match_brute_force() {
+    let rules = RuleSet::new("abcdefg", "fg", 4);
+    for hand in all_hands(rules.order, 4) {
+      let expected = brute_force_shape(&rules, &hand);
+      assert_eq!(rules.shape(&hand), expected, "{}", hand);
+    }
+  }
+
+  #[test]
+  fn other_games() {
+    let rules = RuleSet::new("abcdef", "ef", 3);
+    assert_eq!(rules.shape("aef"), [3]);
+    assert_eq!(rules.shape("abe"), [2, 1]);
+    assert!(rules.key("abe") > rules.key("cba"));
+    assert!(rules.key("aae") < rules.key("aaf"));
+    assert_eq!(rules.key("abcd"), None);
+    assert_eq!(rules.key("abz"), None);
+    let big = RuleSet::new("0123456789", "", 40);
+    assert_eq!(big.key(&"0".repeat(40)), None);
+  }
+}
@@ -0,0 +1,30 @@
+use super::{part_1, part_2};
+use crate::error::SolveError;
+
+/// Line, column and expected construct of the parse error on `input`.
+fn parse_error(input: &str) -> (usize, usize, String) {
+  match part_1(input) {
+    Err(SolveError::Parse(e)) => (e.line, e.column, e.expected),
+    other => panic!("expected a parse error, got {:?}", other),
+  }
+}
+
+#[test]
+fn example() {
+  let input = std::fs::read_to_string("../input/2023/07_a.txt").unwrap();
+  assert_eq!(part_1(&input), Ok(6440));
+  assert_eq!(part_2(&input), Ok(5905));
+}
+
+#[test]
+fn names_the_bad_part_of_a_hand() {
+  let error = |line, column, expected: &str| (line, column, expected.into());
+  assert_eq!(parse_error("32T3K 765\nT55J5"), error(2, 6, "space in bid"));
+  assert_eq!(
+    parse_error("32T3K 765\nT55J5 x"),
+    error(2, 7, "digit in bid")
+  );
+  assert_eq!(parse_error("T55 684"), error(1, 1, "5 cards"));
+  assert_eq!(parse_error("T55J5J 684"), error(1, 1, "5 cards"));
+  assert_eq!(parse_error("T55X5 684"), error(1, 1, "5 cards"));
+}
@@ -1,15 +1,14 @@
 mod parser;
+#[cfg(test)]
+mod tests;
+
 use std::collections::HashMap;
 
 use super::d08::parser::parser as graph_parser;
-
-fn take_dir<T>(c: char, tuple: (T, T)) -> T {
-  match c {
-    'L' => tuple.0,
-    'R' => tuple.1,
-    _ => panic!("Invalid direction"),
-  }
-}
+use crate::{
+  error::SolveError,
+  math::{crt, lcm_all},
+};
 
 fn is_zzz(s: &str) -> bool {
   s == "ZZZ"
@@ -19,66 +18,218 @@ fn ends_with_z(s: &str) -> bool {
   s.ends_with("Z")
 }
 
-fn follow_path(
-  edges: &HashMap<&str, (&str, &str)>,
-  start: &str,
-  end_fn: fn(&str) -> bool,
-  path: &str,
-) -> i64 {
-  let mut count = 0i64;
-  let mut current = start;
-  for dir in path.chars().cycle() {
-    current = take_dir(dir, edges[current]);
-    count += 1;
-    if end_fn(current) {
-      break;
+fn no_solution(why: String) -> SolveError {
+  SolveError::NoSolution(why)
+}
+
+/// Steps at which a walk stands on an end node. The walk is determined by
+/// its state, the node and the index into the instructions, so it runs into
+/// a cycle once a state repeats.
+#[derive(Debug, PartialEq, Eq)]
+struct Walk {
+  /// End steps before the cycle starts.
+  prefix_hits: Vec<i64>,
+  /// First step in the cycle.
+  cycle_start: i64,
+  cycle_len: i64,
+  /// End steps in the first pass through the cycle.
+  cycle_hits: Vec<i64>,
+}
+
+impl Walk {
+  fn is_hit(&self, step: i64) -> bool {
+    if step < self.cycle_start {
+      return self.prefix_hits.binary_search(&step).is_ok();
     }
+    let offset = (step - self.cycle_start) % self.cycle_len;
+    let step = self.cycle_start + offset;
+    self.cycle_hits.binary_search(&step).is_ok()
+  }
+
+  /// End steps below `limit`, in order.
+  fn hits_below(&self, limit: i64) -> impl Iterator<Item = i64> + '_ {
+    let cycles = (0..).map(move |k| k * self.cycle_len);
+    let repeated = cycles
+      .take_while(move |&base| self.cycle_start + base < limit)
+      .flat_map(move |base| self.cycle_hits.iter().map(move |h| h + base));
+    self
+      .prefix_hits
+      .iter()
+      .copied()
+      .chain(repeated)
+      .take_while(move |&step| step < limit)
   }
-  count
 }
 
+/// Nodes interned to dense ids, in input order, with their left and right
+/// successors.
 #[derive(Debug)]
 struct Graph<'a> {
-  edges: HashMap<&'a str, (&'a str, &'a str)>,
+  names: Vec<&'a str>,
+  next: Vec<[u32; 2]>,
 }
 
 impl<'a> Graph<'a> {
-  fn from_list(node_list: Vec<(&'a str, (&'a str, &'a str))>) -> Self {
-    let edges =
-      node_list
-        .into_iter()
-        .fold(HashMap::new(), |mut g, (name, (a, b))| {
-          g.insert(name, (a, b));
-          g
-        });
-    Self { edges }
+  fn from_list(
+    node_list: Vec<(&'a str, (&'a str, &'a str))>,
+  ) -> Result<Self, SolveError> {
+    let ids: HashMap<&str, u32> = node_list
+      .iter()
+      .enumerate()
+      .map(|(id, &(name, _))| (name, id as u32))
+      .collect();
+    let id = |name| {
+      ids
+        .get(name)
+        .copied()
+        .ok_or_else(|| no_solution(format!("no node {}", name)))
+    };
+    let next = node_list
+      .iter()
+      .map(|&(_, (l, r))| Ok([id(l)?, id(r)?]))
+      .collect::<Result<_, SolveError>>()?;
+    let names = node_list.into_iter().map(|(name, _)| name).collect();
+    Ok(Self { names, next })
+  }
+
+  fn walks(
+    &self,
+    is_start: fn(&str) -> bool,
+    is_end: fn(&str) -> bool,
+    path: &str,
+  ) -> Result<Vec<Walk>, SolveError> {
+    let jumps = Jumps::new(self, is_end, path);
+    let mut starts: Vec<u32> = (0..self.names.len() as u32)
+      .filter(|&n| is_start(self.names[n as usize]))
+      .collect();
+    starts.sort_unstable_by_key(|&n| self.names[n as usize]);
+    starts.iter().map(|&s| jumps.walk(self, s)).collect()
   }
+}
+
+/// Where each node ends up after following the whole instruction string, and
+/// the steps on the way at which it stands on an end node.
+struct Jumps {
+  len: i64,
+  to: Vec<u32>,
+  hits: Vec<Vec<i64>>,
+}
+
+impl Jumps {
+  fn new(graph: &Graph, is_end: fn(&str) -> bool, path: &str) -> Self {
+    let ends: Vec<bool> = graph.names.iter().map(|n| is_end(n)).collect();
+    let path: Vec<usize> = path.bytes().map(|c| (c == b'R') as usize).collect();
+    let (to, hits) = (0..graph.names.len() as u32)
+      .map(|mut node| {
+        let mut hits = Vec::new();
+        for (step, &dir) in path.iter().enumerate() {
+          if ends[node as usize] {
+            hits.push(step as i64);
+          }
+          node = graph.next[node as usize][dir];
+        }
+        (node, hits)
+      })
+      .unzip();
+    Jumps {
+      len: path.len() as i64,
+      to,
+      hits,
+    }
+  }
+
+  /// The walk from `start`, one instruction string at a time. The state
+  /// after each whole string is only the node, so the walk cycles as soon
+  /// as a node repeats there.
+  fn walk(&self, graph: &Graph, start: u32) -> Result<Walk, SolveError> {
+    let mut seen = vec![None; self.to.len()];
+    let mut hits = Vec::new();
+    let mut node = start;
+    let mut step = 0;
+    let cycle_start = loop {
+      if let Some(first) = seen[node as usize] {
+        break first;
+      }
+      seen[node as usize] = Some(step);
+      hits.extend(self.hits[node as usize].iter().map(|h| step + h));
+      node = self.to[node as usize];
+      step += self.len;
+    };
+    if hits.is_empty() {
+      let start = graph.names[start as usize];
+      return Err(no_solution(format!("{} never reaches an end", start)));
+    }
+    let split = hits.partition_point(|&h| h < cycle_start);
+    Ok(Walk {
+      cycle_hits: hits.split_off(split),
+      prefix_hits: hits,
+      cycle_start,
+      cycle_len: step - cycle_start,
+    })
+  }
+}
+
+/// First step, after at least one, at which every walk is on an end node.
+fn first_common_hit(walks: &[Walk]) -> Result<i64, SolveError> {
+  let (first, rest) = walks
+    .split_first()
+    .ok_or_else(|| no_solution("no start nodes".to_string()))?;
+  let settled = walks.iter().map(|w| w.cycle_start).max().unwrap().max(1);
 
-  fn follow_path(&self, path: &str) -> i64 {
-    follow_path(&self.edges, "AAA", is_zzz, path)
+  // Before every walk is in its cycle, check the steps one by one
+  let early = first
+    .hits_below(settled)
+    .find(|&step| step >= 1 && rest.iter().all(|w| w.is_hit(step)));
+  if let Some(step) = early {
+    return Ok(step);
   }
 
-  fn follow_path2(&self, path: &str) -> i64 {
-    let cycles = self
-      .edges
-      .keys()
-      .filter(|k| k.ends_with("A"))
-      .map(|n| follow_path(&self.edges, n, ends_with_z, path))
-      .collect::<Vec<_>>();
-    let (x, xs) = cycles.split_at(1);
-    xs.iter()
-      .fold(x[0], |acc, x| num::Integer::lcm(&acc, x))
+  // After that each walk hits an end at some residues of its cycle length
+  let period = lcm_all(walks.iter().map(|w| w.cycle_len))
+    .ok_or_else(|| no_solution("cycle lengths overflow i64".to_string()))?;
+  let mut residues = vec![(0, 1)];
+  for walk in walks {
+    residues = residues
+      .iter()
+      .flat_map(|&(r, m)| {
+        walk
+          .cycle_hits
+          .iter()
+          .filter_map(move |&hit| crt([(r, m), (hit, walk.cycle_len)]))
+      })
+      .collect();
+    residues.sort_unstable();
+    residues.dedup();
   }
+  // Every modulus divides the period, which fits, so crt above only drops
+  // contradicting residues and each one left is modulo the period
+  let first_from_settled = |r: i64| {
+    let cycles = (settled - r).max(0).checked_add(period - 1)? / period;
+    cycles.checked_mul(period)?.checked_add(r)
+  };
+  let steps = residues
+    .into_iter()
+    .map(|(r, m)| {
+      debug_assert_eq!(m, period);
+      first_from_settled(r).ok_or_else(|| {
+        no_solution("the first common step overflows i64".to_string())
+      })
+    })
+    .collect::<Result<Vec<_>, _>>()?;
+  steps
+    .into_iter()
+    .min()
+    .ok_or_else(|| no_solution("the walks never all end together".to_string()))
 }
 
-pub fn part_1(input: &str) -> i64 {
-  let (path, node_list) = graph_parser(input).unwrap();
-  let g = Graph::from_list(node_list);
-  g.follow_path(path)
+pub fn part_1(input: &str) -> Result<i64, SolveError> {
+  let (path, node_list) = graph_parser(input)?;
+  let g = Graph::from_list(node_list)?;
+  first_common_hit(&g.walks(|n| n == "AAA", is_zzz, path)?)
 }
 
-pub fn part_2(input: &str) -> i64 {
-  let (path, node_list) = graph_parser(input).unwrap();
-  let g = Graph::from_list(node_list);
-  g.follow_path2(path)
+pub fn part_2(input: &str) -> Result<i64, SolveError> {
+  let (path, node_list) = graph_parser(input)?;
+  let g = Graph::from_list(node_list)?;
+  first_common_hit(&g.walks(|n| n.ends_with("A"), ends_with_z, path)?)
 }
@@ -1,14 +1,13 @@
 use nom::{
-  branch::alt,
-  bytes::complete::{tag, take_while},
-  character::{
-    complete::{alpha1, space0},
-    streaming::multispace0,
-  },
-  combinator::{complete, opt},
-  multi::many1,
-  sequence::{delimited, separated_pair, terminated, tuple},
-  IResult,
+  bytes::complete::{tag, take_while1},
+  character::complete::{alphanumeric1, space0},
+  error::context,
+  sequence::{delimited, separated_pair, terminated},
+};
+
+use crate::{
+  error::SolveError,
+  parser::{blank_lines, lines, parse_all, ws_line, PResult},
 };
 
 pub type NodeName<'a> = &'a str;
@@ -19,15 +18,15 @@ fn is_path_component(c: char) -> bool {
   c == 'L' || c == 'R'
 }
 
-fn path(s: &str) -> IResult<&str, &str> {
-  terminated(take_while(is_path_component), multispace0)(s)
+fn path(s: &str) -> PResult<'_, &str> {
+  context("path", take_while1(is_path_component))(s)
 }
 
-fn node_name(s: &str) -> IResult<&str, NodeName> {
-  alpha1(s)
+fn node_name(s: &str) -> PResult<'_, NodeName<'_>> {
+  alphanumeric1(s)
 }
 
-fn node_connections(s: &str) -> IResult<&str, NodeConnections> {
+fn node_connections(s: &str) -> PResult<'_, NodeConnections<'_>> {
   delimited(
     tag("("),
     separated_pair(node_name, terminated(tag(","), space0), node_name),
@@ -35,24 +34,13 @@ fn node_connections(s: &str) -> IResult<&str, NodeConnections> {
   )(s)
 }
 
-fn node(s: &str) -> IResult<&str, Node> {
-  terminated(
-    separated_pair(
-      node_name,
-      delimited(space0, tag("="), space0),
-      node_connections,
-    ),
-    opt(complete(multispace0)),
+fn node(s: &str) -> PResult<'_, Node<'_>> {
+  context(
+    "node",
+    separated_pair(node_name, ws_line(tag("=")), node_connections),
   )(s)
 }
 
-fn node_list(s: &str) -> IResult<&str, Vec<Node>> {
-  many1(node)(s)
-}
-
-pub fn parser(
-  s: &str,
-) -> Result<(&str, Vec<Node>), nom::Err<nom::error::Error<&str>>> {
-  let (_, result) = tuple((path, node_list))(s)?;
-  Ok(result)
+pub fn parser(s: &str) -> Result<(&str, Vec<Node<'_>>), SolveError> {
+  parse_all(separated_pair(path, blank_lines, lines(node)), s)
 }
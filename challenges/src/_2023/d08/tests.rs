@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+
+use super::{parser::parser, part_1, part_2};
+
+/// 11A reaches an end every other step from step 2, 22A every third step
+/// from step 1, so the plain lcm of the first hits (6) is wrong.
+const OFFSET_CYCLES: &str = "L
+
+11A = (11B, 11B)
+11B = (11Z, 11Z)
+11Z = (11B, 11B)
+22A = (22Z, 22Z)
+22Z = (22B, 22B)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+";
+
+#[test]
+fn cycles_with_offsets() {
+  assert_eq!(part_2(OFFSET_CYCLES), Ok(4));
+}
+
+#[test]
+fn ends_in_the_prefix() {
+  let input = "L
+
+11A = (11Z, 11Z)
+11Z = (11B, 11B)
+11B = (11C, 11C)
+11C = (11C, 11C)
+22A = (22Z, 22Z)
+22Z = (22Z, 22Z)
+";
+  assert_eq!(part_2(input), Ok(1));
+}
+
+#[test]
+fn contradictory_cycles() {
+  // 11A ends on even steps, 33A on odd ones
+  let input = "L
+
+11A = (11B, 11B)
+11B = (11Z, 11Z)
+11Z = (11B, 11B)
+33A = (33Z, 33Z)
+33Z = (33B, 33B)
+33B = (33Z, 33Z)
+";
+  assert!(part_2(input).is_err());
+}
+
+#[test]
+fn unreachable_end() {
+  let input = "LR
+
+AAA = (BBB, BBB)
+BBB = (BBB, BBB)
+ZZZ = (ZZZ, ZZZ)
+";
+  assert!(part_1(input).is_err());
+  assert!(part_2(input).is_err());
+}
+
+#[test]
+fn missing_node() {
+  assert!(part_1("L\n\nAAA = (BBB, BBB)\n").is_err());
+}
+
+/// Follows the instructions one step at a time until every walk is on an
+/// end node together.
+fn brute_force(input: &str) -> i64 {
+  let (path, node_list) = parser(input).unwrap();
+  let edges: HashMap<_, _> = node_list.into_iter().collect();
+  let mut nodes: Vec<&str> =
+    edges.keys().copied().filter(|n| n.ends_with('A')).collect();
+  for (step, dir) in path.chars().cycle().enumerate() {
+    if nodes.iter().all(|n| n.ends_with('Z')) {
+      return step as i64;
+    }
+    for node in &mut nodes {
+      let (l, r) = edges[node];
+      *node = if dir == 'L' { l } else { r };
+    }
+  }
+  unreachable!()
+}
+
+#[test]
+fn jumps_match_single_steps() {
+  let example = std::fs::read_to_string("../input/2023/08_c.txt").unwrap();
+  for input in [example.as_str(), OFFSET_CYCLES] {
+    assert_eq!(part_2(input), Ok(brute_force(input)));
+  }
+}
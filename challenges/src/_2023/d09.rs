@@ -1,43 +1,54 @@
 // Here goes
 
-fn parse_number_list(s: &str) -> Vec<i64> {
-  s.split(" ").map(|n| n.parse().unwrap()).collect()
-}
+use nom::error::context;
+use num::{BigInt, ToPrimitive};
 
-fn is_all_zero(history: &[i64]) -> bool {
-  history.iter().all(|n| *n == 0)
-}
+use crate::{
+  error::SolveError,
+  parser::{lines, numbers, parse_all, PResult},
+  sequence::Polynomial,
+};
 
-fn diff(history: &[i64]) -> Vec<i64> {
-  history.windows(2).map(|w| w[1] - w[0]).collect()
+fn history(input: &str) -> PResult<'_, Vec<i64>> {
+  context("history", numbers)(input)
 }
 
-fn next_item(history: &[i64]) -> i64 {
-  if is_all_zero(history) {
-    return 0;
+fn extrapolate(
+  input: &str,
+  predict: fn(&Polynomial) -> BigInt,
+) -> Result<i64, SolveError> {
+  let histories = parse_all(lines(history), input)?;
+  let mut sum = BigInt::from(0);
+  for history in histories {
+    // Histories too short to show their degree extend their last level
+    let polynomial = Polynomial::interpolate(&history)
+      .ok_or_else(|| SolveError::NoSolution("empty history".to_string()))?;
+    sum += predict(&polynomial);
   }
-  history.last().unwrap() + next_item(diff(history).as_slice())
+  sum
+    .to_i64()
+    .ok_or_else(|| SolveError::NoSolution("sum overflows i64".to_string()))
 }
 
-fn prev_item(history: &[i64]) -> i64 {
-  if is_all_zero(history) {
-    return 0;
-  }
-  history.first().unwrap() - prev_item(diff(history).as_slice())
+pub fn part_1(input: &str) -> Result<i64, SolveError> {
+  extrapolate(input, |p| p.forward(1))
 }
 
-pub fn part_1(input: &str) -> i64 {
-  input
-    .lines()
-    .map(parse_number_list)
-    .map(|h| next_item(h.as_slice()))
-    .sum()
+pub fn part_2(input: &str) -> Result<i64, SolveError> {
+  extrapolate(input, |p| p.backward(1))
 }
 
-pub fn part_2(input: &str) -> i64 {
-  input
-    .lines()
-    .map(parse_number_list)
-    .map(|h| prev_item(h.as_slice()))
-    .sum()
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn short_histories_extend_their_last_level() {
+    assert_eq!(part_1("7"), Ok(7));
+    assert_eq!(part_2("7"), Ok(7));
+    assert_eq!(part_1("3 5"), Ok(7));
+    assert_eq!(part_2("3 5"), Ok(1));
+    assert_eq!(part_1("1 2 4 8 16"), Ok(31));
+    assert_eq!(part_2("1 2 4 8 16"), Ok(1));
+  }
 }
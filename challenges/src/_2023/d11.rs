@@ -1,102 +1,120 @@
-use std::collections::{BTreeSet, HashMap};
-
-use itertools::Itertools;
-use num::iter::Range;
-
-use crate::common::*;
-
-#[derive(Debug)]
-struct Coord {
-  x: i64,
-  y: i64,
+use crate::{
+  error::SolveError,
+  grid::{Grid, Point},
+  params::{Param, ParamSpec, Params},
+};
+
+/// How many times as wide each empty row and column grows in part 2.
+pub const MULTIPLIER: Param<i64> =
+  Param::checked("multiplier", 1_000_000, |&m| m >= 1);
+
+pub const PARAMS: &[&dyn ParamSpec] = &[&MULTIPLIER];
+
+/// Distances between every pair of galaxies along one axis, split into the
+/// lines crossed and how many of those are empty, which expansion widens.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct AxisSum {
+  lines: u64,
+  empty: u64,
 }
 
-impl Coord {
-  fn new(x: i64, y: i64) -> Self {
-    Coord { x, y }
+/// Counts the galaxies on each line, then walks the lines in order keeping
+/// prefix sums of the galaxies seen so far.
+fn axis_sum(coords: impl Iterator<Item = usize>, len: usize) -> AxisSum {
+  let mut counts = vec![0u64; len];
+  for c in coords {
+    counts[c] += 1;
   }
-
-  fn manhattan(&self, other: &Coord) -> i64 {
-    (self.x - other.x).abs() + (self.y - other.y).abs()
+  let mut sum = AxisSum::default();
+  let (mut seen, mut empty_lines) = (0, 0);
+  let (mut seen_lines, mut seen_empty) = (0, 0);
+  for (line, count) in (0..).zip(counts) {
+    if count == 0 {
+      empty_lines += 1;
+      continue;
+    }
+    sum.lines += count * (seen * line - seen_lines);
+    sum.empty += count * (seen * empty_lines - seen_empty);
+    seen += count;
+    seen_lines += count * line;
+    seen_empty += count * empty_lines;
   }
+  sum
 }
 
-fn adjustment_map(
-  set: &BTreeSet<usize>,
-  value: usize,
-) -> Vec<(std::ops::Range<usize>, usize)> {
-  set
-    .iter()
-    .cloned()
-    .collect::<Vec<_>>()
-    .windows(2)
-    .enumerate()
-    .map(|(i, w)| (w[0]..w[1], (i + 1) * (value - 1)))
-    .collect()
+/// Sum of the distances between every pair of galaxies once each empty row
+/// and column is `factor` times as wide.
+fn distance_sum(grid: &Grid<char>, factor: i64) -> Result<i64, SolveError> {
+  let galaxies: Vec<Point> = grid
+    .iter_with_points()
+    .filter(|&(_, &c)| c == '#')
+    .map(|(p, _)| p)
+    .collect();
+  let x = axis_sum(galaxies.iter().map(|p| p.x as usize), grid.width());
+  let y = axis_sum(galaxies.iter().map(|p| p.y as usize), grid.height());
+  let widened = (x.empty + y.empty) as i128 * (factor as i128 - 1);
+  let sum = (x.lines + y.lines) as i128 + widened;
+  i64::try_from(sum).map_err(|_| {
+    SolveError::NoSolution(format!("distance sum {} overflows i64", sum))
+  })
 }
 
-fn adjust(map: &[(std::ops::Range<usize>, usize)], idx: usize) -> usize {
-  map
-    .iter()
-    .find(|(range, _)| range.contains(&idx))
-    .map(|(_, v)| idx + *v)
-    .unwrap_or(idx)
+pub fn part_1(input: &str) -> Result<i64, SolveError> {
+  distance_sum(&input.parse()?, 2)
 }
 
-#[derive(Debug)]
-struct Stars {
-  stars: Vec<Coord>,
-  stars_adjusted: Vec<Coord>,
+pub fn part_2(input: &str, params: &Params) -> Result<i64, SolveError> {
+  distance_sum(&input.parse()?, MULTIPLIER.get(params)?)
 }
 
-impl Stars {
-  fn from_grid_input(grid: &GridInput, multiplier: usize) -> Self {
-    let mut empty_column_idxs: BTreeSet<usize> = (0..grid.width + 1).collect();
-    let mut empty_row_idxs: BTreeSet<usize> = (0..grid.height + 1).collect();
-    let mut stars = Vec::new();
-
-    for (x, y, c) in grid.iter_with_coords() {
-      if c == '#' {
-        stars.push(Coord::new(x as i64, y as i64));
-        empty_column_idxs.remove(&x);
-        empty_row_idxs.remove(&y);
-      }
-    }
+#[cfg(test)]
+mod tests {
+  use super::*;
 
-    let column_map = adjustment_map(&empty_column_idxs, multiplier);
-    let row_map = adjustment_map(&empty_row_idxs, multiplier);
+  fn example() -> Grid<char> {
+    let input = std::fs::read_to_string("../input/2023/11_a.txt").unwrap();
+    input.parse().unwrap()
+  }
 
-    let mut stars_adjusted = Vec::new();
-    for star in &stars {
-      stars_adjusted.push(Coord::new(
-        adjust(&column_map, star.x as usize) as i64,
-        adjust(&row_map, star.y as usize) as i64,
-      ));
+  /// Expands the coordinates and sums the distances of every pair.
+  fn pairwise(grid: &Grid<char>, factor: i64) -> i64 {
+    let empty_row = |y| grid.row(y).iter().all(|&c| c != '#');
+    let empty_column = |x| grid.column(x).all(|&c| c != '#');
+    let expand = |empty: &dyn Fn(usize) -> bool, c: isize| {
+      let empties = (0..c as usize).filter(|&i| empty(i)).count() as i64;
+      c as i64 + empties * (factor - 1)
+    };
+    let galaxies: Vec<(i64, i64)> = grid
+      .iter_with_points()
+      .filter(|&(_, &c)| c == '#')
+      .map(|(p, _)| (expand(&empty_column, p.x), expand(&empty_row, p.y)))
+      .collect();
+    let mut sum = 0;
+    for (i, a) in galaxies.iter().enumerate() {
+      for b in &galaxies[i + 1..] {
+        sum += (a.0 - b.0).abs() + (a.1 - b.1).abs();
+      }
     }
+    sum
+  }
 
-    Self {
-      stars,
-      stars_adjusted,
+  #[test]
+  fn matches_pairwise_sum() {
+    let grid = example();
+    assert_eq!(distance_sum(&grid, 10), Ok(1030));
+    assert_eq!(distance_sum(&grid, 100), Ok(8410));
+    for factor in [1, 2, 3, 10, 1_000_000] {
+      assert_eq!(distance_sum(&grid, factor), Ok(pairwise(&grid, factor)));
     }
   }
 
-  fn distance_sum(&self) -> i64 {
-    self
-      .stars_adjusted
-      .iter()
-      .combinations(2)
-      .map(|xs| xs[0].manhattan(xs[1]))
-      .sum()
+  #[test]
+  fn huge_factors() {
+    let grid = example();
+    // 82 empty lines crossed in all, plus 292 lines
+    let factor = (i64::MAX - 292) / 82 + 1;
+    assert_eq!(distance_sum(&grid, factor), Ok(292 + 82 * (factor - 1)));
+    assert!(distance_sum(&grid, factor + 1).is_err());
+    assert!(distance_sum(&grid, i64::MAX).is_err());
   }
 }
-
-pub fn part_1(input: &str) -> i64 {
-  let starfield = Stars::from_grid_input(&GridInput::from_str(input), 2);
-  starfield.distance_sum()
-}
-
-pub fn part_2(input: &str) -> i64 {
-  let starfield =
-    Stars::from_grid_input(&GridInput::from_str(input), 1_000_000);
-  starfield.distance_sum()
-}
@@ -0,0 +1,47 @@
+use nom::{
+  character::complete::{alpha1, space1},
+  error::context,
+  multi::separated_list1,
+};
+
+use crate::{
+  error::SolveError,
+  graph::Graph,
+  parser::{key_values, lines, parse_all, PResult},
+};
+
+type Component<'a> = (&'a str, Vec<&'a str>);
+
+fn component(s: &str) -> PResult<'_, Component<'_>> {
+  context(
+    "component",
+    key_values(alpha1, separated_list1(space1, alpha1)),
+  )(s)
+}
+
+fn parse(input: &str) -> Result<Graph<'_>, SolveError> {
+  let components = parse_all(lines(component), input)?;
+  Ok(
+    components
+      .into_iter()
+      .fold(Graph::new(), |mut g, (name, connected)| {
+        for other in connected {
+          g.add_edge(name, other);
+        }
+        g
+      }),
+  )
+}
+
+pub fn part_1(input: &str) -> Result<i64, SolveError> {
+  let cut = parse(input)?.min_cut().ok_or_else(|| {
+    SolveError::NoSolution("fewer than two components".to_string())
+  })?;
+  if cut.weight != 3 {
+    return Err(SolveError::NoSolution(format!(
+      "minimum cut has {} wires, expected 3",
+      cut.weight
+    )));
+  }
+  Ok((cut.sizes.0 * cut.sizes.1) as i64)
+}
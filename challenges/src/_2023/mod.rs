@@ -1,3 +1,5 @@
+use crate::challenge::{DaySolvers, Solver, YearSolvers};
+
 pub mod d01;
 pub mod d03;
 pub mod d04;
@@ -7,3 +9,24 @@ pub mod d08;
 pub mod d09;
 pub mod d10;
 pub mod d11;
+pub mod d25;
+
+pub const SOLVERS: YearSolvers = YearSolvers::new(
+  2023,
+  &[
+    DaySolvers::new(1, &[d01::part_1, d01::part_2]),
+    DaySolvers::new(3, &[d03::part_1, d03::part_2]),
+    DaySolvers::new(4, &[d04::part_1, d04::part_2]),
+    DaySolvers::new(5, &[d05::part_1, d05::part_2]),
+    DaySolvers::new(7, &[d07::part_1, d07::part_2]),
+    DaySolvers::new(8, &[d08::part_1, d08::part_2]),
+    DaySolvers::new(9, &[d09::part_1, d09::part_2]),
+    DaySolvers::new(10, &[d10::part_1, d10::part_2]),
+    DaySolvers::with_params(
+      11,
+      d11::PARAMS,
+      &[Solver::Plain(d11::part_1), Solver::WithParams(d11::part_2)],
+    ),
+    DaySolvers::new(25, &[d25::part_1]),
+  ],
+);
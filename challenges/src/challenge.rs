@@ -0,0 +1,293 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{
+  error::SolveError,
+  params::{ParamSpec, Params},
+};
+
+/// First year with puzzles.
+const FIRST_YEAR: u16 = 2015;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+  pub const fn new(year: u16) -> Option<Self> {
+    match year >= FIRST_YEAR {
+      true => Some(Year(year)),
+      false => None,
+    }
+  }
+
+  pub const fn get(self) -> u16 {
+    self.0
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Day(u8);
+
+impl Day {
+  pub const fn new(day: u8) -> Option<Self> {
+    match day {
+      1..=25 => Some(Day(day)),
+      _ => None,
+    }
+  }
+
+  pub const fn get(self) -> u8 {
+    self.0
+  }
+}
+
+const PARTS: [Part; 2] = [Part::One, Part::Two];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+  One,
+  Two,
+}
+
+impl Part {
+  pub const fn new(part: u8) -> Option<Self> {
+    match part {
+      1 => Some(Part::One),
+      2 => Some(Part::Two),
+      _ => None,
+    }
+  }
+
+  pub const fn get(self) -> u8 {
+    match self {
+      Part::One => 1,
+      Part::Two => 2,
+    }
+  }
+}
+
+/// One part of one day's puzzle, written `year/day/part`, e.g. `2023/1/2`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Challenge {
+  pub year: Year,
+  pub day: Day,
+  pub part: Part,
+}
+
+impl Challenge {
+  pub const fn new(year: Year, day: Day, part: Part) -> Self {
+    Challenge { year, day, part }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChallengeError {
+  /// Not three numbers separated by `/`.
+  Format(String),
+  Year(String),
+  Day(String),
+  Part(String),
+}
+
+impl Display for ChallengeError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      ChallengeError::Format(s) => {
+        write!(f, "Expected year/day/part, e.g. 2023/1/2, got {:?}", s)
+      }
+      ChallengeError::Year(s) => {
+        write!(f, "Year must be {} or later, got {:?}", FIRST_YEAR, s)
+      }
+      ChallengeError::Day(s) => write!(f, "Day must be 1 to 25, got {:?}", s),
+      ChallengeError::Part(s) => write!(f, "Part must be 1 or 2, got {:?}", s),
+    }
+  }
+}
+
+impl std::error::Error for ChallengeError {}
+
+fn parse_with<T>(
+  s: &str,
+  new: fn(u8) -> Option<T>,
+  err: fn(String) -> ChallengeError,
+) -> Result<T, ChallengeError> {
+  s.parse().ok().and_then(new).ok_or_else(|| err(s.into()))
+}
+
+impl FromStr for Year {
+  type Err = ChallengeError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let year = s.parse().ok().and_then(Year::new);
+    year.ok_or_else(|| ChallengeError::Year(s.into()))
+  }
+}
+
+impl FromStr for Day {
+  type Err = ChallengeError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    parse_with(s, Day::new, ChallengeError::Day)
+  }
+}
+
+impl FromStr for Part {
+  type Err = ChallengeError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    parse_with(s, Part::new, ChallengeError::Part)
+  }
+}
+
+impl FromStr for Challenge {
+  type Err = ChallengeError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let fields: Vec<&str> = s.split('/').collect();
+    let [year, day, part] = fields[..] else {
+      return Err(ChallengeError::Format(s.into()));
+    };
+    Ok(Challenge::new(year.parse()?, day.parse()?, part.parse()?))
+  }
+}
+
+impl Display for Year {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    self.0.fmt(f)
+  }
+}
+
+impl Display for Day {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    self.0.fmt(f)
+  }
+}
+
+impl Display for Part {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    self.get().fmt(f)
+  }
+}
+
+impl Display for Challenge {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}/{}/{}", self.year, self.day, self.part)
+  }
+}
+
+pub type PlainSolver = fn(&str) -> Result<i64, SolveError>;
+pub type ParamSolver = fn(&str, &Params) -> Result<i64, SolveError>;
+
+/// Solver of one part, reading the day's parameters or not.
+#[derive(Clone, Copy)]
+pub enum Solver {
+  Plain(PlainSolver),
+  WithParams(ParamSolver),
+}
+
+impl Solver {
+  pub fn solve(self, input: &str, params: &Params) -> Result<i64, SolveError> {
+    match self {
+      Solver::Plain(solve) => solve(input),
+      Solver::WithParams(solve) => solve(input, params),
+    }
+  }
+}
+
+enum Parts {
+  Plain(&'static [PlainSolver]),
+  Mixed(&'static [Solver]),
+}
+
+/// Solvers of one day, part 1 first. Days without a second part, or whose
+/// second part is not solved yet, list only one.
+pub struct DaySolvers {
+  day: Day,
+  parts: Parts,
+  params: &'static [&'static dyn ParamSpec],
+}
+
+const fn day_or_panic(day: u8, parts: usize) -> Day {
+  assert!(matches!(parts, 1 | 2), "A day has one or two parts");
+  match Day::new(day) {
+    Some(day) => day,
+    None => panic!("Day must be 1 to 25"),
+  }
+}
+
+impl DaySolvers {
+  pub const fn new(day: u8, parts: &'static [PlainSolver]) -> Self {
+    DaySolvers {
+      day: day_or_panic(day, parts.len()),
+      parts: Parts::Plain(parts),
+      params: &[],
+    }
+  }
+
+  /// A day whose solvers may read the listed parameters.
+  pub const fn with_params(
+    day: u8,
+    params: &'static [&'static dyn ParamSpec],
+    parts: &'static [Solver],
+  ) -> Self {
+    DaySolvers {
+      day: day_or_panic(day, parts.len()),
+      parts: Parts::Mixed(parts),
+      params,
+    }
+  }
+
+  fn len(&self) -> usize {
+    match self.parts {
+      Parts::Plain(parts) => parts.len(),
+      Parts::Mixed(parts) => parts.len(),
+    }
+  }
+
+  fn part(&self, part: Part) -> Option<Solver> {
+    let i = part.get() as usize - 1;
+    match self.parts {
+      Parts::Plain(parts) => parts.get(i).map(|&s| Solver::Plain(s)),
+      Parts::Mixed(parts) => parts.get(i).copied(),
+    }
+  }
+}
+
+/// Registry of a year's solved days, exported by each year module.
+pub struct YearSolvers {
+  year: Year,
+  days: &'static [DaySolvers],
+}
+
+impl YearSolvers {
+  pub const fn new(year: u16, days: &'static [DaySolvers]) -> Self {
+    match Year::new(year) {
+      Some(year) => YearSolvers { year, days },
+      None => panic!("Year before the first puzzles"),
+    }
+  }
+
+  pub fn year(&self) -> Year {
+    self.year
+  }
+
+  fn day(&self, day: Day) -> Option<&DaySolvers> {
+    self.days.iter().find(|d| d.day == day)
+  }
+
+  pub fn solver(&self, day: Day, part: Part) -> Option<Solver> {
+    self.day(day)?.part(part)
+  }
+
+  /// Parameters the day's solvers read.
+  pub fn params(&self, day: Day) -> &'static [&'static dyn ParamSpec] {
+    self.day(day).map_or(&[], |d| d.params)
+  }
+
+  pub fn challenges(&self) -> impl Iterator<Item = Challenge> + '_ {
+    self.days.iter().flat_map(move |d| {
+      PARTS[..d.len()]
+        .iter()
+        .map(move |&part| Challenge::new(self.year, d.day, part))
+    })
+  }
+}
@@ -0,0 +1,51 @@
+//! Randomized differential testing: feed the same generated inputs to a fast
+//! and a naive implementation and check that they agree.
+
+use std::{fmt::Debug, ops::Range};
+
+/// Small deterministic PRNG (SplitMix64), so failures reproduce from a seed.
+pub struct Rng(u64);
+
+impl Rng {
+  pub fn new(seed: u64) -> Self {
+    Rng(seed)
+  }
+
+  pub fn next_u64(&mut self) -> u64 {
+    self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = self.0;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+  }
+
+  pub fn range(&mut self, range: Range<i64>) -> i64 {
+    let len = range.end.abs_diff(range.start);
+    range.start + (self.next_u64() % len) as i64
+  }
+
+  pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+    &items[self.range(0..items.len() as i64) as usize]
+  }
+}
+
+/// Runs `cases` generated inputs through both implementations, panicking
+/// with the first input they disagree on.
+pub fn check<I: Debug, O: PartialEq + Debug>(
+  seed: u64,
+  cases: usize,
+  mut generate: impl FnMut(&mut Rng) -> I,
+  fast: impl Fn(&I) -> O,
+  naive: impl Fn(&I) -> O,
+) {
+  let mut rng = Rng::new(seed);
+  for case in 0..cases {
+    let input = generate(&mut rng);
+    let (expected, actual) = (naive(&input), fast(&input));
+    assert_eq!(
+      actual, expected,
+      "case {} (seed {}) disagrees on input:\n{:?}",
+      case, seed, input
+    );
+  }
+}
@@ -0,0 +1,209 @@
+use std::fmt::Display;
+
+use nom::error::{ErrorKind, VerboseError, VerboseErrorKind};
+
+use crate::{
+  challenge::Challenge, grid::GridError, params::ParamError, pipes::MazeError,
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SolveError {
+  Parse(ParseError),
+  Grid(GridError),
+  Maze(MazeError),
+  Param(ParamError),
+  /// No solver is registered for the challenge.
+  Unsolved(Challenge),
+  /// The input is well formed but has no answer.
+  NoSolution(String),
+}
+
+impl Display for SolveError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      SolveError::Parse(e) => e.fmt(f),
+      SolveError::Grid(e) => e.fmt(f),
+      SolveError::Maze(e) => e.fmt(f),
+      SolveError::Param(e) => e.fmt(f),
+      SolveError::Unsolved(c) => write!(f, "No solution for {} yet", c),
+      SolveError::NoSolution(why) => write!(f, "No answer: {}", why),
+    }
+  }
+}
+
+impl std::error::Error for SolveError {}
+
+impl From<ParseError> for SolveError {
+  fn from(e: ParseError) -> Self {
+    SolveError::Parse(e)
+  }
+}
+
+impl From<GridError> for SolveError {
+  fn from(e: GridError) -> Self {
+    SolveError::Grid(e)
+  }
+}
+
+impl From<MazeError> for SolveError {
+  fn from(e: MazeError) -> Self {
+    SolveError::Maze(e)
+  }
+}
+
+impl From<ParamError> for SolveError {
+  fn from(e: ParamError) -> Self {
+    SolveError::Param(e)
+  }
+}
+
+/// Parse failure located in the original input, with 1-based line and
+/// column.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError {
+  pub line: usize,
+  pub column: usize,
+  /// The offending line, without its line break.
+  pub source_line: String,
+  /// What the parser was looking for, e.g. `digit in "mapping"`.
+  pub expected: String,
+}
+
+fn describe(kind: &VerboseErrorKind) -> String {
+  match kind {
+    VerboseErrorKind::Context(context) => context.to_string(),
+    VerboseErrorKind::Char(c) => format!("'{}'", c.escape_default()),
+    VerboseErrorKind::Nom(kind) => kind.description().to_lowercase(),
+  }
+}
+
+impl ParseError {
+  /// Locates the unparsed `rest` of `input`.
+  pub fn new(input: &str, rest: &str, expected: String) -> Self {
+    let offset = input.len() - rest.len();
+    let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = input[offset..]
+      .find('\n')
+      .map_or(input.len(), |i| offset + i);
+    ParseError {
+      line: input[..offset].matches('\n').count() + 1,
+      column: input[line_start..offset].chars().count() + 1,
+      source_line: input[line_start..line_end].trim_end_matches('\r').into(),
+      expected,
+    }
+  }
+
+  /// Reports the innermost failure, named after the innermost context
+  /// around it.
+  pub fn from_verbose(input: &str, e: VerboseError<&str>) -> Self {
+    let Some((rest, kind)) = e.errors.first() else {
+      return ParseError::new(input, input, "valid input".into());
+    };
+    let context = e.errors.iter().find_map(|(_, kind)| match kind {
+      VerboseErrorKind::Context(context) => Some(context),
+      _ => None,
+    });
+    let expected = match (kind, context) {
+      (VerboseErrorKind::Context(_), _) | (_, None) => describe(kind),
+      // A rejected value says no more than the context around the check
+      (
+        VerboseErrorKind::Nom(
+          ErrorKind::MapOpt | ErrorKind::MapRes | ErrorKind::Verify,
+        ),
+        Some(context),
+      ) => context.to_string(),
+      (_, Some(context)) => format!("{} in {}", describe(kind), context),
+    };
+    ParseError::new(input, rest, expected)
+  }
+}
+
+impl Display for ParseError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let gutter = " ".repeat(self.line.to_string().len());
+    writeln!(
+      f,
+      "Parse error at line {}, column {}: expected {}",
+      self.line, self.column, self.expected
+    )?;
+    writeln!(f, "{} |", gutter)?;
+    writeln!(f, "{} | {}", self.line, self.source_line)?;
+    write!(f, "{} | {}^", gutter, " ".repeat(self.column - 1))
+  }
+}
+
+impl std::error::Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+  use nom::{character::complete::char, error::context};
+
+  use super::*;
+  use crate::parser::{lines, parse_all, unsigned, PResult};
+
+  /// Locates the error at byte `offset` of `input`.
+  fn at(input: &str, offset: usize) -> (usize, usize, String) {
+    let e = ParseError::new(input, &input[offset..], "x".into());
+    (e.line, e.column, e.source_line)
+  }
+
+  #[test]
+  fn locates_first_and_last_lines() {
+    let input = "ab\ncd";
+    assert_eq!(at(input, 0), (1, 1, "ab".into()));
+    assert_eq!(at(input, 1), (1, 2, "ab".into()));
+    // The line break belongs to the line it ends
+    assert_eq!(at(input, 2), (1, 3, "ab".into()));
+    assert_eq!(at(input, 4), (2, 2, "cd".into()));
+    assert_eq!(at(input, 5), (2, 3, "cd".into()));
+    assert_eq!(at("ab\n", 3), (2, 1, "".into()));
+  }
+
+  #[test]
+  fn strips_carriage_returns() {
+    let input = "ab\r\ncd\r\nef";
+    assert_eq!(at(input, 5), (2, 2, "cd".into()));
+    assert_eq!(at(input, 6), (2, 3, "cd".into()));
+    assert_eq!(at(input, 10), (3, 3, "ef".into()));
+  }
+
+  #[test]
+  fn counts_columns_in_chars() {
+    let input = "1\né€x";
+    let offset = input.find('x').unwrap();
+    assert_eq!(at(input, offset), (2, 3, "é€x".into()));
+    let report = ParseError::new(input, &input[offset..], "digit".into());
+    assert_eq!(
+      report.to_string(),
+      "Parse error at line 2, column 3: expected digit\n  |\n2 | é€x\n  |   ^"
+    );
+  }
+
+  #[test]
+  fn widens_gutter_for_long_line_numbers() {
+    let input = "1\n".repeat(11) + "1 2";
+    let report = ParseError::new(&input, &input[23..], "end".into());
+    assert_eq!(
+      report.to_string(),
+      "Parse error at line 12, column 2: expected end\n   |\n12 | 1 2\n   |  ^"
+    );
+  }
+
+  #[test]
+  fn names_innermost_context() {
+    fn number(input: &str) -> PResult<'_, u32> {
+      context("number", unsigned)(input)
+    }
+    let err = |input| match parse_all(lines(number), input) {
+      Err(SolveError::Parse(e)) => (e.line, e.column, e.expected),
+      other => panic!("expected a parse error, got {:?}", other),
+    };
+    assert_eq!(err("1\n2\nx"), (3, 1, "digit in number".into()));
+    assert_eq!(err("1\n2 3"), (2, 3, "end of file".into()));
+    let plus = |input| parse_all(context("plus", char('+')), input);
+    match plus("-") {
+      Err(SolveError::Parse(e)) => assert_eq!(e.expected, "'+' in plus"),
+      other => panic!("expected a parse error, got {:?}", other),
+    }
+  }
+}
@@ -0,0 +1,301 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use num::{PrimInt, Signed};
+
+/// 2D point. Grids put the origin at the top-left corner with `y` growing
+/// downwards, so "clockwise" below is as seen on screen.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug, Default)]
+pub struct Point<T = isize> {
+  pub x: T,
+  pub y: T,
+}
+
+impl<T> Point<T> {
+  pub const fn new(x: T, y: T) -> Self {
+    Point { x, y }
+  }
+}
+
+impl<T: Copy + Neg<Output = T>> Point<T> {
+  pub fn rotate_cw(&self) -> Self {
+    Point::new(-self.y, self.x)
+  }
+
+  pub fn rotate_ccw(&self) -> Self {
+    Point::new(self.y, -self.x)
+  }
+}
+
+impl<T: Copy + Signed + Ord> Point<T> {
+  pub fn manhattan(&self, other: &Self) -> T {
+    (self.x - other.x).abs() + (self.y - other.y).abs()
+  }
+
+  pub fn chebyshev(&self, other: &Self) -> T {
+    (self.x - other.x).abs().max((self.y - other.y).abs())
+  }
+}
+
+impl<T: PrimInt> Point<T> {
+  /// Row-major index into a `width` x `height` grid, `None` if out of bounds.
+  pub fn to_index(self, width: usize, height: usize) -> Option<usize> {
+    let x = self.x.to_usize()?;
+    let y = self.y.to_usize()?;
+    (x < width && y < height).then_some(y * width + x)
+  }
+
+  /// Inverse of [`Point::to_index`], `None` for a zero `width` or if a
+  /// coordinate does not fit in `T`.
+  pub fn from_index(index: usize, width: usize) -> Option<Self> {
+    if width == 0 {
+      return None;
+    }
+    Some(Point::new(T::from(index % width)?, T::from(index / width)?))
+  }
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
+  type Output = Self;
+
+  fn add(self, other: Self) -> Self {
+    Point::new(self.x + other.x, self.y + other.y)
+  }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+  type Output = Self;
+
+  fn sub(self, other: Self) -> Self {
+    Point::new(self.x - other.x, self.y - other.y)
+  }
+}
+
+impl<T: Neg<Output = T>> Neg for Point<T> {
+  type Output = Self;
+
+  fn neg(self) -> Self {
+    Point::new(-self.x, -self.y)
+  }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point<T> {
+  type Output = Self;
+
+  fn mul(self, k: T) -> Self {
+    Point::new(self.x * k, self.y * k)
+  }
+}
+
+impl<T: Copy + Add<Output = T>> AddAssign for Point<T> {
+  fn add_assign(&mut self, other: Self) {
+    *self = *self + other;
+  }
+}
+
+impl<T: Copy + Sub<Output = T>> SubAssign for Point<T> {
+  fn sub_assign(&mut self, other: Self) {
+    *self = *self - other;
+  }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+  fn from((x, y): (T, T)) -> Self {
+    Point::new(x, y)
+  }
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug, Default)]
+pub struct Point3<T = isize> {
+  pub x: T,
+  pub y: T,
+  pub z: T,
+}
+
+impl<T> Point3<T> {
+  pub const fn new(x: T, y: T, z: T) -> Self {
+    Point3 { x, y, z }
+  }
+}
+
+impl<T: Copy + Signed + Ord> Point3<T> {
+  pub fn manhattan(&self, other: &Self) -> T {
+    (self.x - other.x).abs()
+      + (self.y - other.y).abs()
+      + (self.z - other.z).abs()
+  }
+
+  pub fn chebyshev(&self, other: &Self) -> T {
+    (self.x - other.x)
+      .abs()
+      .max((self.y - other.y).abs())
+      .max((self.z - other.z).abs())
+  }
+}
+
+impl<T: Add<Output = T>> Add for Point3<T> {
+  type Output = Self;
+
+  fn add(self, other: Self) -> Self {
+    Point3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+  }
+}
+
+impl<T: Sub<Output = T>> Sub for Point3<T> {
+  type Output = Self;
+
+  fn sub(self, other: Self) -> Self {
+    Point3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+  }
+}
+
+impl<T: Neg<Output = T>> Neg for Point3<T> {
+  type Output = Self;
+
+  fn neg(self) -> Self {
+    Point3::new(-self.x, -self.y, -self.z)
+  }
+}
+
+impl<T> From<(T, T, T)> for Point3<T> {
+  fn from((x, y, z): (T, T, T)) -> Self {
+    Point3::new(x, y, z)
+  }
+}
+
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum Direction {
+  North,
+  East,
+  South,
+  West,
+}
+
+impl Direction {
+  /// Clockwise from north.
+  pub const ALL: [Direction; 4] = [
+    Direction::North,
+    Direction::East,
+    Direction::South,
+    Direction::West,
+  ];
+
+  pub const fn turn_right(self) -> Self {
+    match self {
+      Direction::North => Direction::East,
+      Direction::East => Direction::South,
+      Direction::South => Direction::West,
+      Direction::West => Direction::North,
+    }
+  }
+
+  pub const fn turn_left(self) -> Self {
+    match self {
+      Direction::North => Direction::West,
+      Direction::East => Direction::North,
+      Direction::South => Direction::East,
+      Direction::West => Direction::South,
+    }
+  }
+
+  pub const fn reverse(self) -> Self {
+    match self {
+      Direction::North => Direction::South,
+      Direction::East => Direction::West,
+      Direction::South => Direction::North,
+      Direction::West => Direction::East,
+    }
+  }
+
+  /// Unit step in this direction.
+  pub fn delta<T: Signed>(self) -> Point<T> {
+    match self {
+      Direction::North => Point::new(T::zero(), -T::one()),
+      Direction::East => Point::new(T::one(), T::zero()),
+      Direction::South => Point::new(T::zero(), T::one()),
+      Direction::West => Point::new(-T::one(), T::zero()),
+    }
+  }
+
+  pub fn from_delta<T: Signed + PartialEq>(delta: Point<T>) -> Option<Self> {
+    Direction::ALL.into_iter().find(|d| d.delta::<T>() == delta)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn rotations_follow_the_screen() {
+    for d in Direction::ALL {
+      let delta: Point = d.delta();
+      assert_eq!(delta.rotate_cw(), d.turn_right().delta());
+      assert_eq!(delta.rotate_ccw(), d.turn_left().delta());
+    }
+    // y points down, so east turns clockwise into south
+    assert_eq!(Point::new(1, 0).rotate_cw(), Point::new(0, 1));
+    assert_eq!(Point::new(2, -3).rotate_ccw(), Point::new(-3, -2));
+    assert_eq!(
+      Point::new(2, -3).rotate_cw().rotate_ccw(),
+      Point::new(2, -3)
+    );
+  }
+
+  #[test]
+  fn turns() {
+    assert_eq!(Direction::North.turn_right(), Direction::East);
+    assert_eq!(Direction::North.turn_left(), Direction::West);
+    for d in Direction::ALL {
+      assert_eq!(d.turn_right().turn_left(), d);
+      assert_eq!(d.turn_right().turn_right(), d.reverse());
+      assert_eq!(d.turn_left().turn_left().turn_left(), d.turn_right());
+    }
+  }
+
+  #[test]
+  fn directions_from_deltas() {
+    for d in Direction::ALL {
+      assert_eq!(Direction::from_delta(d.delta::<i64>()), Some(d));
+    }
+    assert_eq!(
+      Direction::from_delta(Point::new(0, -1)),
+      Some(Direction::North)
+    );
+    assert_eq!(Direction::from_delta(Point::new(1, 1)), None);
+    assert_eq!(Direction::from_delta(Point::new(0, 2)), None);
+    assert_eq!(Direction::from_delta(Point::new(0, 0)), None);
+  }
+
+  #[test]
+  fn indices() {
+    assert_eq!(Point::new(2, 1).to_index(3, 2), Some(5));
+    assert_eq!(Point::new(0, 0).to_index(3, 2), Some(0));
+    assert_eq!(Point::new(-1, 0).to_index(3, 2), None);
+    assert_eq!(Point::new(0, -1).to_index(3, 2), None);
+    assert_eq!(Point::new(3, 0).to_index(3, 2), None);
+    assert_eq!(Point::new(0, 2).to_index(3, 2), None);
+    assert_eq!(Point::new(0, 0).to_index(0, 0), None);
+    assert_eq!(Point::<i32>::from_index(5, 3), Some(Point::new(2, 1)));
+    assert_eq!(Point::<u8>::from_index(300, 1), None);
+    assert_eq!(Point::<i32>::from_index(0, 0), None);
+  }
+
+  #[test]
+  fn distances() {
+    let (a, b) = (Point::new(1, -2), Point::new(-3, 4));
+    assert_eq!(a.manhattan(&b), 10);
+    assert_eq!(a.chebyshev(&b), 6);
+    assert_eq!(b.chebyshev(&a), 6);
+    assert_eq!(a.chebyshev(&a), 0);
+  }
+
+  #[test]
+  fn points_3d() {
+    let (a, b) = (Point3::new(1, 2, 3), Point3::from((-1, 4, 9)));
+    assert_eq!(a + b, Point3::new(0, 6, 12));
+    assert_eq!(a - b, Point3::new(2, -2, -6));
+    assert_eq!(-a, Point3::new(-1, -2, -3));
+    assert_eq!(a.manhattan(&b), 10);
+    assert_eq!(a.chebyshev(&b), 6);
+  }
+}
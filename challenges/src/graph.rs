@@ -0,0 +1,245 @@
+use std::collections::{BinaryHeap, HashMap};
+
+pub type NodeId = usize;
+
+/// Undirected weighted graph whose nodes are interned names.
+#[derive(Debug, Default)]
+pub struct Graph<'a> {
+  names: Vec<&'a str>,
+  ids: HashMap<&'a str, NodeId>,
+  edges: Vec<HashMap<NodeId, u64>>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct MinCut {
+  pub weight: u64,
+  pub sizes: (usize, usize),
+}
+
+impl<'a> Graph<'a> {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn intern(&mut self, name: &'a str) -> NodeId {
+    if let Some(&id) = self.ids.get(name) {
+      return id;
+    }
+    let id = self.names.len();
+    self.names.push(name);
+    self.ids.insert(name, id);
+    self.edges.push(HashMap::new());
+    id
+  }
+
+  pub fn add_edge(&mut self, a: &'a str, b: &'a str) {
+    let a = self.intern(a);
+    let b = self.intern(b);
+    if a == b {
+      return;
+    }
+    *self.edges[a].entry(b).or_insert(0) += 1;
+    *self.edges[b].entry(a).or_insert(0) += 1;
+  }
+
+  pub fn len(&self) -> usize {
+    self.names.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.names.is_empty()
+  }
+
+  pub fn id(&self, name: &str) -> Option<NodeId> {
+    self.ids.get(name).copied()
+  }
+
+  pub fn name(&self, id: NodeId) -> &'a str {
+    self.names[id]
+  }
+
+  pub fn neighbours(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+    self.edges[id].keys().copied()
+  }
+
+  /// Global minimum cut (Stoer-Wagner).
+  ///
+  /// Returns `None` for graphs with less than two nodes, which cannot be cut.
+  pub fn min_cut(&self) -> Option<MinCut> {
+    let n = self.len();
+    if n < 2 {
+      return None;
+    }
+
+    let mut edges = self.edges.clone();
+    let mut sizes = vec![1usize; n];
+    let mut active: Vec<NodeId> = (0..n).collect();
+    let mut best: Option<(u64, usize)> = None;
+
+    let mut keys = vec![0u64; n];
+    let mut added = vec![false; n];
+    while active.len() > 1 {
+      for &v in &active {
+        keys[v] = 0;
+        added[v] = false;
+      }
+
+      // Maximum adjacency ordering, remembering the last two nodes added
+      let mut heap = BinaryHeap::from([(0u64, active[0])]);
+      let (mut s, mut t) = (active[0], active[0]);
+      let mut remaining = active.len();
+      while remaining > 0 {
+        let v = match heap.pop() {
+          Some((key, v)) if added[v] || key != keys[v] => continue,
+          Some((_, v)) => v,
+          // Disconnected graph: continue from any node not yet added
+          None => *active.iter().find(|&&v| !added[v]).unwrap(),
+        };
+        added[v] = true;
+        remaining -= 1;
+        (s, t) = (t, v);
+        for (&u, &w) in &edges[v] {
+          if !added[u] {
+            keys[u] += w;
+            heap.push((keys[u], u));
+          }
+        }
+      }
+
+      if best.is_none_or(|(weight, _)| keys[t] < weight) {
+        best = Some((keys[t], sizes[t]));
+      }
+
+      // Merge t into s
+      let t_edges = std::mem::take(&mut edges[t]);
+      for (u, w) in t_edges {
+        edges[u].remove(&t);
+        if u != s {
+          *edges[s].entry(u).or_insert(0) += w;
+          *edges[u].entry(s).or_insert(0) += w;
+        }
+      }
+      sizes[s] += sizes[t];
+      active.retain(|&v| v != t);
+    }
+
+    best.map(|(weight, size)| MinCut {
+      weight,
+      sizes: (size, n - size),
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const NAMES: [&str; 6] = ["a", "b", "c", "d", "e", "f"];
+
+  fn graph(n: usize, edges: &[(usize, usize)]) -> Graph<'static> {
+    let mut graph = Graph::new();
+    for name in &NAMES[..n] {
+      graph.intern(name);
+    }
+    for &(a, b) in edges {
+      graph.add_edge(NAMES[a], NAMES[b]);
+    }
+    graph
+  }
+
+  /// Lightest cut over every split of the nodes into two non-empty sides,
+  /// only those with `size` nodes on one side if given.
+  fn brute_force(
+    n: usize,
+    edges: &[(usize, usize)],
+    size: Option<usize>,
+  ) -> Option<u64> {
+    (1..(1u32 << n) - 1)
+      .filter(|side| size.is_none_or(|s| side.count_ones() as usize == s))
+      .map(|side| {
+        let crosses =
+          |&&(a, b): &&(usize, usize)| (side >> a & 1) != (side >> b & 1);
+        edges.iter().filter(crosses).count() as u64
+      })
+      .min()
+  }
+
+  #[test]
+  fn needs_two_nodes() {
+    assert_eq!(graph(0, &[]).min_cut(), None);
+    assert_eq!(graph(1, &[(0, 0)]).min_cut(), None);
+  }
+
+  #[test]
+  fn cuts_bridge() {
+    // Two triangles joined by the edge c-d
+    let edges = [(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 5), (5, 3)];
+    let cut = graph(6, &edges).min_cut();
+    assert_eq!(
+      cut,
+      Some(MinCut {
+        weight: 1,
+        sizes: (3, 3)
+      })
+    );
+  }
+
+  #[test]
+  fn counts_multi_edges() {
+    let cut = graph(2, &[(0, 1), (1, 0), (0, 1)]).min_cut();
+    assert_eq!(
+      cut,
+      Some(MinCut {
+        weight: 3,
+        sizes: (1, 1)
+      })
+    );
+  }
+
+  #[test]
+  fn splits_disconnected() {
+    let cut = graph(5, &[(0, 1), (1, 2), (3, 4), (4, 3)])
+      .min_cut()
+      .unwrap();
+    assert_eq!(cut.weight, 0);
+    let mut sizes = [cut.sizes.0, cut.sizes.1];
+    sizes.sort();
+    assert_eq!(sizes, [2, 3]);
+  }
+
+  #[test]
+  fn matches_brute_force() {
+    // Every graph on up to five nodes, also with some edges doubled
+    for n in 2..=5 {
+      let pairs: Vec<(usize, usize)> = (0..n)
+        .flat_map(|a| (a + 1..n).map(move |b| (a, b)))
+        .collect();
+      for set in 0..1u32 << pairs.len() {
+        let mut edges: Vec<(usize, usize)> = (0..pairs.len())
+          .filter(|i| set >> i & 1 == 1)
+          .map(|i| pairs[i])
+          .collect();
+        for doubled in [false, true] {
+          if doubled {
+            edges.extend(edges.clone().iter().step_by(2));
+          }
+          let cut = graph(n, &edges).min_cut().unwrap();
+          let context = format!("{} nodes, edges {:?}", n, edges);
+          assert_eq!(
+            Some(cut.weight),
+            brute_force(n, &edges, None),
+            "{}",
+            context
+          );
+          assert_eq!(cut.sizes.0 + cut.sizes.1, n, "{}", context);
+          assert_eq!(
+            brute_force(n, &edges, Some(cut.sizes.0)),
+            Some(cut.weight),
+            "{}",
+            context
+          );
+        }
+      }
+    }
+  }
+}
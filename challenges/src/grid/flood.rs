@@ -0,0 +1,219 @@
+use std::collections::VecDeque;
+
+use super::{Direction, Grid, Point};
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Connectivity {
+  /// Orthogonal neighbours only.
+  Four,
+  /// Orthogonal and diagonal neighbours.
+  Eight,
+}
+
+/// Connected components of a grid, see [`Grid::components`].
+#[derive(Debug)]
+pub struct Components {
+  /// Component id of every passable cell.
+  pub labels: Grid<Option<usize>>,
+  /// Number of cells in each component, indexed by id.
+  pub sizes: Vec<usize>,
+}
+
+impl Components {
+  pub fn len(&self) -> usize {
+    self.sizes.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.sizes.is_empty()
+  }
+
+  /// Mask of the cells belonging to component `id`.
+  pub fn region(&self, id: usize) -> Grid<bool> {
+    self.labels.map(|label| *label == Some(id))
+  }
+}
+
+impl<T> Grid<T> {
+  pub fn neighbours(
+    &self,
+    p: Point,
+    connectivity: Connectivity,
+  ) -> Box<dyn Iterator<Item = Point> + '_> {
+    match connectivity {
+      Connectivity::Four => Box::new(self.neighbours4(p)),
+      Connectivity::Eight => Box::new(self.neighbours8(p)),
+    }
+  }
+
+  /// Every point on the outer edge of the grid, each exactly once.
+  pub fn edge_points(&self) -> impl Iterator<Item = Point> + '_ {
+    let (w, h) = (self.width as isize, self.height as isize);
+    self
+      .points()
+      .filter(move |p| p.x == 0 || p.y == 0 || p.x == w - 1 || p.y == h - 1)
+  }
+
+  /// Marks every cell reachable from `starts` through cells accepted by
+  /// `passable`. Impassable starts are ignored.
+  pub fn flood_fill(
+    &self,
+    starts: impl IntoIterator<Item = Point>,
+    connectivity: Connectivity,
+    mut passable: impl FnMut(Point, &T) -> bool,
+  ) -> Grid<bool> {
+    let mut reached = self.map(|_| false);
+    let mut queue = VecDeque::new();
+    for start in starts {
+      if self.get(start).is_some_and(|cell| passable(start, cell))
+        && !reached[start]
+      {
+        reached[start] = true;
+        queue.push_back(start);
+      }
+    }
+    while let Some(p) = queue.pop_front() {
+      for n in self.neighbours(p, connectivity) {
+        if !reached[n] && passable(n, &self[n]) {
+          reached[n] = true;
+          queue.push_back(n);
+        }
+      }
+    }
+    reached
+  }
+
+  /// Labels the connected regions of passable cells, ids are assigned in
+  /// row-major order of each region's first cell.
+  pub fn components(
+    &self,
+    connectivity: Connectivity,
+    mut passable: impl FnMut(Point, &T) -> bool,
+  ) -> Components {
+    let open =
+      Grid::from_fn(self.width, self.height, |p| passable(p, &self[p]));
+    let mut labels = self.map(|_| None);
+    let mut sizes = Vec::new();
+    let mut stack = Vec::new();
+    for start in self.points() {
+      if !open[start] || labels[start].is_some() {
+        continue;
+      }
+      let id = sizes.len();
+      let mut size = 0;
+      labels[start] = Some(id);
+      stack.push(start);
+      while let Some(p) = stack.pop() {
+        size += 1;
+        for n in self.neighbours(p, connectivity) {
+          if open[n] && labels[n].is_none() {
+            labels[n] = Some(id);
+            stack.push(n);
+          }
+        }
+      }
+      sizes.push(size);
+    }
+    Components { labels, sizes }
+  }
+}
+
+impl Grid<bool> {
+  pub fn count(&self) -> usize {
+    self.iter().filter(|&&set| set).count()
+  }
+
+  /// Set cells with at least one orthogonal neighbour outside the region,
+  /// the edge of the grid counting as outside.
+  pub fn boundary(&self) -> Vec<Point> {
+    self
+      .iter_with_points()
+      .filter(|&(p, &set)| {
+        set
+          && Direction::ALL
+            .into_iter()
+            .any(|d| !self.get(p + d.delta()).copied().unwrap_or(false))
+      })
+      .map(|(p, _)| p)
+      .collect()
+  }
+
+  /// Number of cell sides separating the region from the rest of the plane.
+  pub fn perimeter(&self) -> usize {
+    self
+      .iter_with_points()
+      .filter(|&(_, &set)| set)
+      .map(|(p, _)| {
+        Direction::ALL
+          .into_iter()
+          .filter(|d| !self.get(p + d.delta()).copied().unwrap_or(false))
+          .count()
+      })
+      .sum()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const MASK: &str = "\
+##..
+#..#
+..##
+.#..
+";
+
+  fn mask(s: &str) -> Grid<bool> {
+    Grid::parse(s, |c| c == '#').unwrap()
+  }
+
+  fn labels(components: &Components) -> String {
+    let glyph = |label: &Option<usize>| match label {
+      Some(id) => char::from_digit(*id as u32, 10).unwrap(),
+      None => '.',
+    };
+    components.labels.map(glyph).to_string()
+  }
+
+  #[test]
+  fn components_by_connectivity() {
+    let grid = mask(MASK);
+    let four = grid.components(Connectivity::Four, |_, &set| set);
+    assert_eq!(four.sizes, [3, 3, 1]);
+    assert_eq!(labels(&four), "00..\n0..1\n..11\n.2..\n");
+    // The lone cell touches the second region diagonally
+    let eight = grid.components(Connectivity::Eight, |_, &set| set);
+    assert_eq!(eight.sizes, [3, 4]);
+    assert_eq!(labels(&eight), "00..\n0..1\n..11\n.1..\n");
+    assert_eq!(eight.region(1).count(), 4);
+    let none = grid.components(Connectivity::Four, |_, _| false);
+    assert!(none.is_empty());
+  }
+
+  #[test]
+  fn flood_respects_connectivity() {
+    let grid = mask(MASK);
+    let start = [Point::new(3, 1)];
+    let four = grid.flood_fill(start, Connectivity::Four, |_, &set| set);
+    assert_eq!(four.count(), 3);
+    let eight = grid.flood_fill(start, Connectivity::Eight, |_, &set| set);
+    assert_eq!(eight.count(), 4);
+    let blocked =
+      grid.flood_fill([Point::new(2, 0)], Connectivity::Four, |_, &set| set);
+    assert_eq!(blocked.count(), 0);
+  }
+
+  #[test]
+  fn boundary_and_perimeter() {
+    let block = mask("###.\n###.\n###.\n....\n");
+    let boundary = block.boundary();
+    assert_eq!(boundary.len(), 8);
+    assert!(!boundary.contains(&Point::new(1, 1)));
+    assert_eq!(block.perimeter(), 12);
+    // Two L-shaped regions and a single cell
+    assert_eq!(mask(MASK).perimeter(), 8 + 8 + 4);
+    assert_eq!(mask(MASK).boundary().len(), 7);
+    assert_eq!(mask("#").perimeter(), 4);
+  }
+}
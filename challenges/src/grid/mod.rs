@@ -0,0 +1,318 @@
+use std::{
+  fmt::{Debug, Display},
+  ops::{Index, IndexMut},
+  str::FromStr,
+};
+
+use crate::geom::Direction;
+pub use crate::geom::Point;
+
+mod flood;
+mod sparse;
+#[cfg(test)]
+mod tests;
+mod tiled;
+
+pub use flood::{Components, Connectivity};
+pub use sparse::{Bounds, SparseGrid};
+pub use tiled::Tiled;
+
+const NEIGHBOURS_8: [Point; 8] = [
+  Point::new(-1, -1),
+  Point::new(0, -1),
+  Point::new(1, -1),
+  Point::new(1, 0),
+  Point::new(1, 1),
+  Point::new(0, 1),
+  Point::new(-1, 1),
+  Point::new(-1, 0),
+];
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum GridError {
+  NonRectangular {
+    line: usize,
+    expected: usize,
+    found: usize,
+  },
+}
+
+impl Display for GridError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      GridError::NonRectangular {
+        line,
+        expected,
+        found,
+      } => write!(
+        f,
+        "Non-rectangular grid: line {} has {} cells, expected {}",
+        line, found, expected
+      ),
+    }
+  }
+}
+
+impl std::error::Error for GridError {}
+
+/// Dense, row-major 2D grid.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+  width: usize,
+  height: usize,
+  cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+  /// Parses one cell per character, one row per line.
+  pub fn parse(
+    input: &str,
+    mut cell: impl FnMut(char) -> T,
+  ) -> Result<Self, GridError> {
+    let mut width = 0;
+    let mut height = 0;
+    let mut cells = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+      let len = cells.len();
+      cells.extend(line.chars().map(&mut cell));
+      let found = cells.len() - len;
+      if i == 0 {
+        width = found;
+      } else if found != width {
+        return Err(GridError::NonRectangular {
+          line: i + 1,
+          expected: width,
+          found,
+        });
+      }
+      height += 1;
+    }
+    Ok(Grid {
+      width,
+      height,
+      cells,
+    })
+  }
+
+  pub fn from_fn(
+    width: usize,
+    height: usize,
+    mut f: impl FnMut(Point) -> T,
+  ) -> Self {
+    let cells = (0..height)
+      .flat_map(|y| (0..width).map(move |x| Point::new(x as isize, y as isize)))
+      .map(&mut f)
+      .collect();
+    Grid {
+      width,
+      height,
+      cells,
+    }
+  }
+
+  pub fn width(&self) -> usize {
+    self.width
+  }
+
+  pub fn height(&self) -> usize {
+    self.height
+  }
+
+  pub fn in_bounds(&self, p: Point) -> bool {
+    self.index_of(p).is_some()
+  }
+
+  pub fn index_of(&self, p: Point) -> Option<usize> {
+    p.to_index(self.width, self.height)
+  }
+
+  /// Point of the cell at row-major `index`, `None` past the last cell.
+  pub fn point_of(&self, index: usize) -> Option<Point> {
+    if index >= self.cells.len() {
+      return None;
+    }
+    Point::from_index(index, self.width)
+  }
+
+  pub fn get(&self, p: Point) -> Option<&T> {
+    self.index_of(p).map(|i| &self.cells[i])
+  }
+
+  pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+    self.index_of(p).map(|i| &mut self.cells[i])
+  }
+
+  /// Replaces the cell at `p`, returning the old value if `p` is in bounds.
+  pub fn set(&mut self, p: Point, value: T) -> Option<T> {
+    self.get_mut(p).map(|cell| std::mem::replace(cell, value))
+  }
+
+  pub fn iter(&self) -> impl Iterator<Item = &T> {
+    self.cells.iter()
+  }
+
+  pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+    self.cells.iter_mut()
+  }
+
+  pub fn points(&self) -> impl Iterator<Item = Point> {
+    let width = self.width;
+    (0..self.height).flat_map(move |y| {
+      (0..width).map(move |x| Point::new(x as isize, y as isize))
+    })
+  }
+
+  pub fn iter_with_points(&self) -> impl Iterator<Item = (Point, &T)> {
+    self.points().zip(self.cells.iter())
+  }
+
+  pub fn position(&self, pred: impl FnMut(&T) -> bool) -> Option<Point> {
+    self
+      .cells
+      .iter()
+      .position(pred)
+      .and_then(|i| self.point_of(i))
+  }
+
+  /// In-bounds orthogonal neighbours of `p`, clockwise from north.
+  pub fn neighbours4(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+    Direction::ALL
+      .into_iter()
+      .map(move |d| p + d.delta())
+      .filter(|&n| self.in_bounds(n))
+  }
+
+  /// In-bounds orthogonal and diagonal neighbours of `p`, clockwise from
+  /// north-west.
+  pub fn neighbours8(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+    NEIGHBOURS_8
+      .into_iter()
+      .map(move |d| p + d)
+      .filter(|&n| self.in_bounds(n))
+  }
+
+  pub fn row(&self, y: usize) -> &[T] {
+    &self.cells[y * self.width..(y + 1) * self.width]
+  }
+
+  pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+    // `chunks` panics on a zero chunk size, an empty grid has no rows anyway
+    self.cells.chunks(self.width.max(1))
+  }
+
+  pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+    assert!(x < self.width, "column {} out of bounds", x);
+    self.cells.iter().skip(x).step_by(self.width.max(1))
+  }
+
+  pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+    (0..self.width).map(move |x| self.column(x))
+  }
+
+  pub fn tiled(&self) -> Option<Tiled<'_, T>> {
+    Tiled::new(self)
+  }
+
+  pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+    Grid {
+      width: self.width,
+      height: self.height,
+      cells: self.cells.iter().map(f).collect(),
+    }
+  }
+}
+
+impl<T: Clone> Grid<T> {
+  pub fn new(width: usize, height: usize, value: T) -> Self {
+    Grid {
+      width,
+      height,
+      cells: vec![value; width * height],
+    }
+  }
+
+  fn remap(
+    &self,
+    width: usize,
+    height: usize,
+    f: impl Fn(Point) -> Point,
+  ) -> Self {
+    Grid::from_fn(width, height, |p| self[f(p)].clone())
+  }
+
+  pub fn transpose(&self) -> Self {
+    self.remap(self.height, self.width, |p| Point::new(p.y, p.x))
+  }
+
+  pub fn rotate_cw(&self) -> Self {
+    let h = self.height as isize;
+    self.remap(self.height, self.width, |p| Point::new(p.y, h - 1 - p.x))
+  }
+
+  pub fn rotate_ccw(&self) -> Self {
+    let w = self.width as isize;
+    self.remap(self.height, self.width, |p| Point::new(w - 1 - p.y, p.x))
+  }
+
+  /// Mirrors left to right.
+  pub fn flip_horizontal(&self) -> Self {
+    let w = self.width as isize;
+    self.remap(self.width, self.height, |p| Point::new(w - 1 - p.x, p.y))
+  }
+
+  /// Mirrors top to bottom.
+  pub fn flip_vertical(&self) -> Self {
+    let h = self.height as isize;
+    self.remap(self.width, self.height, |p| Point::new(p.x, h - 1 - p.y))
+  }
+}
+
+impl<T> Index<Point> for Grid<T> {
+  type Output = T;
+
+  fn index(&self, p: Point) -> &T {
+    match self.index_of(p) {
+      Some(i) => &self.cells[i],
+      None => panic!("{:?} out of bounds", p),
+    }
+  }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+  fn index_mut(&mut self, p: Point) -> &mut T {
+    match self.index_of(p) {
+      Some(i) => &mut self.cells[i],
+      None => panic!("{:?} out of bounds", p),
+    }
+  }
+}
+
+impl FromStr for Grid<char> {
+  type Err = GridError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    Grid::parse(s, |c| c)
+  }
+}
+
+impl<T: Display> Display for Grid<T> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    for row in self.rows() {
+      for cell in row {
+        write!(f, "{}", cell)?;
+      }
+      writeln!(f)?;
+    }
+    Ok(())
+  }
+}
+
+impl<T: Debug> Debug for Grid<T> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("Grid")
+      .field("width", &self.width)
+      .field("height", &self.height)
+      .field("rows", &self.rows().collect::<Vec<_>>())
+      .finish()
+  }
+}
@@ -0,0 +1,265 @@
+use std::{collections::HashMap, fmt::Display};
+
+use super::{Grid, Point};
+
+/// Inclusive bounding box.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Bounds {
+  pub min: Point,
+  pub max: Point,
+}
+
+impl Bounds {
+  pub fn new(p: Point) -> Self {
+    Bounds { min: p, max: p }
+  }
+
+  pub fn width(&self) -> usize {
+    (self.max.x - self.min.x + 1) as usize
+  }
+
+  pub fn height(&self) -> usize {
+    (self.max.y - self.min.y + 1) as usize
+  }
+
+  pub fn contains(&self, p: Point) -> bool {
+    (self.min.x..=self.max.x).contains(&p.x)
+      && (self.min.y..=self.max.y).contains(&p.y)
+  }
+
+  pub fn extend(&mut self, p: Point) {
+    self.min = Point::new(self.min.x.min(p.x), self.min.y.min(p.y));
+    self.max = Point::new(self.max.x.max(p.x), self.max.y.max(p.y));
+  }
+
+  fn on_edge(&self, p: Point) -> bool {
+    p.x == self.min.x
+      || p.x == self.max.x
+      || p.y == self.min.y
+      || p.y == self.max.y
+  }
+
+  /// Row-major points inside the box.
+  pub fn points(&self) -> impl Iterator<Item = Point> {
+    let Bounds { min, max } = *self;
+    (min.y..=max.y)
+      .flat_map(move |y| (min.x..=max.x).map(move |x| Point::new(x, y)))
+  }
+}
+
+/// Grid that only stores occupied cells, for unbounded or mostly empty
+/// coordinate spaces.
+#[derive(Clone, Debug)]
+pub struct SparseGrid<T> {
+  cells: HashMap<Point, T>,
+  bounds: Option<Bounds>,
+}
+
+impl<T> Default for SparseGrid<T> {
+  fn default() -> Self {
+    SparseGrid {
+      cells: HashMap::new(),
+      bounds: None,
+    }
+  }
+}
+
+impl<T> SparseGrid<T> {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Keeps the cells of a dense grid for which `f` returns a value.
+  pub fn from_grid<U>(
+    grid: &Grid<U>,
+    mut f: impl FnMut(&U) -> Option<T>,
+  ) -> Self {
+    grid
+      .iter_with_points()
+      .filter_map(|(p, cell)| f(cell).map(|v| (p, v)))
+      .collect()
+  }
+
+  pub fn len(&self) -> usize {
+    self.cells.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.cells.is_empty()
+  }
+
+  /// Bounding box of the occupied cells, `None` when empty.
+  pub fn bounds(&self) -> Option<Bounds> {
+    self.bounds
+  }
+
+  pub fn contains(&self, p: Point) -> bool {
+    self.cells.contains_key(&p)
+  }
+
+  pub fn get(&self, p: Point) -> Option<&T> {
+    self.cells.get(&p)
+  }
+
+  pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+    self.cells.get_mut(&p)
+  }
+
+  pub fn insert(&mut self, p: Point, value: T) -> Option<T> {
+    match &mut self.bounds {
+      Some(bounds) => bounds.extend(p),
+      None => self.bounds = Some(Bounds::new(p)),
+    }
+    self.cells.insert(p, value)
+  }
+
+  pub fn remove(&mut self, p: Point) -> Option<T> {
+    let value = self.cells.remove(&p)?;
+    // Only removing a cell on the edge can shrink the box
+    if self.bounds.is_some_and(|b| b.on_edge(p)) {
+      self.bounds = self.cells.keys().fold(None, |bounds, &p| match bounds {
+        Some(mut b) => {
+          b.extend(p);
+          Some(b)
+        }
+        None => Some(Bounds::new(p)),
+      });
+    }
+    Some(value)
+  }
+
+  /// Occupied cells in row-major order.
+  pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+    let mut cells = self.cells.iter().map(|(&p, v)| (p, v)).collect::<Vec<_>>();
+    cells.sort_unstable_by_key(|(p, _)| (p.y, p.x));
+    cells.into_iter()
+  }
+
+  pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+    self.iter().map(|(p, _)| p)
+  }
+
+  /// Draws the bounding box, using `empty` for unoccupied cells.
+  pub fn render(
+    &self,
+    empty: char,
+    mut cell: impl FnMut(&T) -> char,
+  ) -> String {
+    let Some(bounds) = self.bounds else {
+      return String::new();
+    };
+    let mut out = String::with_capacity((bounds.width() + 1) * bounds.height());
+    for p in bounds.points() {
+      out.push(self.get(p).map_or(empty, &mut cell));
+      if p.x == bounds.max.x {
+        out.push('\n');
+      }
+    }
+    out
+  }
+}
+
+impl<T: Clone> SparseGrid<T> {
+  /// Dense copy of the bounding box, with the box's minimum corner moved to
+  /// the origin.
+  pub fn to_grid(&self, empty: T) -> Grid<T> {
+    match self.bounds {
+      Some(b) => Grid::from_fn(b.width(), b.height(), |p| {
+        self
+          .get(p + b.min)
+          .cloned()
+          .unwrap_or_else(|| empty.clone())
+      }),
+      None => Grid::new(0, 0, empty),
+    }
+  }
+}
+
+impl<T> FromIterator<(Point, T)> for SparseGrid<T> {
+  fn from_iter<I: IntoIterator<Item = (Point, T)>>(iter: I) -> Self {
+    iter
+      .into_iter()
+      .fold(SparseGrid::new(), |mut grid, (p, v)| {
+        grid.insert(p, v);
+        grid
+      })
+  }
+}
+
+impl<T: Display> Display for SparseGrid<T> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let Some(bounds) = self.bounds else {
+      return Ok(());
+    };
+    for p in bounds.points() {
+      match self.get(p) {
+        Some(v) => write!(f, "{}", v)?,
+        None => write!(f, ".")?,
+      }
+      if p.x == bounds.max.x {
+        writeln!(f)?;
+      }
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn sparse(points: &[(isize, isize)]) -> SparseGrid<char> {
+    points.iter().map(|&p| (Point::from(p), '#')).collect()
+  }
+
+  fn bounds(min: (isize, isize), max: (isize, isize)) -> Option<Bounds> {
+    Some(Bounds {
+      min: min.into(),
+      max: max.into(),
+    })
+  }
+
+  #[test]
+  fn remove_shrinks_bounds() {
+    let mut grid = sparse(&[(-2, 1), (0, 0), (3, -1), (1, 1)]);
+    assert_eq!(grid.bounds(), bounds((-2, -1), (3, 1)));
+    // Inside the box, nothing changes
+    assert_eq!(grid.remove(Point::new(0, 0)), Some('#'));
+    assert_eq!(grid.bounds(), bounds((-2, -1), (3, 1)));
+    assert_eq!(grid.remove(Point::new(3, -1)), Some('#'));
+    assert_eq!(grid.bounds(), bounds((-2, 1), (1, 1)));
+    assert_eq!(grid.remove(Point::new(3, -1)), None);
+    grid.remove(Point::new(-2, 1));
+    assert_eq!(grid.bounds(), bounds((1, 1), (1, 1)));
+    grid.remove(Point::new(1, 1));
+    assert_eq!(grid.bounds(), None);
+    assert!(grid.is_empty());
+  }
+
+  #[test]
+  fn renders_bounding_box() {
+    let mut grid = sparse(&[(-1, -1), (1, 0)]);
+    grid.insert(Point::new(0, 0), 'x');
+    assert_eq!(grid.to_string(), "#..\n.x#\n");
+    assert_eq!(grid.render(' ', |&c| c.to_ascii_uppercase()), "#  \n X#\n");
+    assert_eq!(SparseGrid::<char>::new().to_string(), "");
+    assert_eq!(SparseGrid::<char>::new().render('.', |&c| c), "");
+  }
+
+  #[test]
+  fn to_grid_moves_min_to_origin() {
+    let grid = sparse(&[(-3, 2), (-1, 4)]);
+    let dense = grid.to_grid('.');
+    assert_eq!(dense.to_string(), "#..\n...\n..#\n");
+    assert_eq!(dense[Point::new(0, 0)], '#');
+    assert_eq!(dense[Point::new(2, 2)], '#');
+    let round_trip =
+      SparseGrid::from_grid(&dense, |&c| (c == '#').then_some(c));
+    assert_eq!(
+      round_trip.points().collect::<Vec<_>>(),
+      [(0, 0), (2, 2)].map(Point::from)
+    );
+    let empty = SparseGrid::<char>::new().to_grid('.');
+    assert_eq!((empty.width(), empty.height()), (0, 0));
+  }
+}
@@ -0,0 +1,113 @@
+use super::*;
+
+fn grid(s: &str) -> Grid<char> {
+  s.parse().unwrap()
+}
+
+fn points(ps: &[(isize, isize)]) -> Vec<Point> {
+  ps.iter().copied().map(Point::from).collect()
+}
+
+#[test]
+fn rejects_ragged_rows() {
+  let err = "ab\nc\nde".parse::<Grid<char>>().unwrap_err();
+  assert_eq!(
+    err,
+    GridError::NonRectangular {
+      line: 2,
+      expected: 2,
+      found: 1,
+    }
+  );
+  assert_eq!(
+    err.to_string(),
+    "Non-rectangular grid: line 2 has 1 cells, expected 2"
+  );
+  assert!("ab\ncd\n".parse::<Grid<char>>().is_ok());
+  assert!("abc\ncd".parse::<Grid<char>>().is_err());
+}
+
+#[test]
+fn displays_rows() {
+  assert_eq!(grid("ab\ncd\nef").to_string(), "ab\ncd\nef\n");
+  let empty = grid("");
+  assert_eq!((empty.width(), empty.height()), (0, 0));
+  assert_eq!(empty.to_string(), "");
+  let numbers = Grid::from_fn(3, 2, |p| p.x + p.y);
+  assert_eq!(numbers.to_string(), "012\n123\n");
+}
+
+#[test]
+fn transforms() {
+  let g = grid("ab\ncd\nef");
+  assert_eq!(g.rotate_cw(), grid("eca\nfdb"));
+  assert_eq!(g.rotate_ccw(), grid("bdf\nace"));
+  assert_eq!(g.transpose(), grid("ace\nbdf"));
+  assert_eq!(g.flip_horizontal(), grid("ba\ndc\nfe"));
+  assert_eq!(g.flip_vertical(), grid("ef\ncd\nab"));
+  assert_eq!(g.rotate_cw().rotate_ccw(), g);
+  assert_eq!(
+    g.rotate_cw().rotate_cw(),
+    g.flip_horizontal().flip_vertical()
+  );
+}
+
+#[test]
+fn rows_and_columns() {
+  let g = grid("ab\ncd\nef");
+  let rows: Vec<String> = g.rows().map(|r| r.iter().collect()).collect();
+  assert_eq!(rows, ["ab", "cd", "ef"]);
+  let columns: Vec<String> = g.columns().map(|c| c.collect()).collect();
+  assert_eq!(columns, ["ace", "bdf"]);
+  assert_eq!(g.column(1).collect::<String>(), "bdf");
+  assert_eq!(grid("").columns().count(), 0);
+}
+
+#[test]
+fn neighbour_order() {
+  let g = grid("ab\ncd\nef");
+  let at = Point::new(0, 1);
+  assert_eq!(
+    g.neighbours4(at).collect::<Vec<_>>(),
+    points(&[(0, 0), (1, 1), (0, 2)])
+  );
+  assert_eq!(
+    g.neighbours8(at).collect::<Vec<_>>(),
+    points(&[(0, 0), (1, 0), (1, 1), (1, 2), (0, 2)])
+  );
+  let centre = Grid::new(3, 3, ());
+  assert_eq!(
+    centre.neighbours8(Point::new(1, 1)).collect::<Vec<_>>(),
+    points(&[
+      (0, 0),
+      (1, 0),
+      (2, 0),
+      (2, 1),
+      (2, 2),
+      (1, 2),
+      (0, 2),
+      (0, 1),
+    ])
+  );
+}
+
+#[test]
+fn out_of_bounds() {
+  let mut g = grid("ab\ncd");
+  assert_eq!(g.get(Point::new(-1, 0)), None);
+  assert_eq!(g.get(Point::new(2, 0)), None);
+  assert_eq!(g.set(Point::new(1, 1), 'x'), Some('d'));
+  assert_eq!(g.set(Point::new(0, 2), 'x'), None);
+  assert_eq!(g.position(|&c| c == 'x'), Some(Point::new(1, 1)));
+  assert_eq!(g.point_of(3), Some(Point::new(1, 1)));
+  assert_eq!(g.point_of(4), None);
+  let empty = Grid::new(0, 3, 'x');
+  assert_eq!(empty.point_of(0), None);
+  assert_eq!(empty.position(|_| true), None);
+}
+
+#[test]
+#[should_panic(expected = "column 2 out of bounds")]
+fn column_out_of_bounds() {
+  grid("ab\ncd").column(2).count();
+}
@@ -0,0 +1,66 @@
+use super::{Direction, Grid, Point};
+
+/// View of a non-empty grid repeated infinitely in every direction.
+#[derive(Clone, Copy, Debug)]
+pub struct Tiled<'a, T> {
+  grid: &'a Grid<T>,
+}
+
+impl<'a, T> Tiled<'a, T> {
+  /// `None` for a grid without cells, which cannot tile the plane.
+  pub fn new(grid: &'a Grid<T>) -> Option<Self> {
+    (grid.width() > 0 && grid.height() > 0).then_some(Tiled { grid })
+  }
+
+  /// Position of `p` within its tile.
+  pub fn wrap(&self, p: Point) -> Point {
+    Point::new(
+      p.x.rem_euclid(self.grid.width() as isize),
+      p.y.rem_euclid(self.grid.height() as isize),
+    )
+  }
+
+  /// Which copy of the grid `p` falls into, the original being `(0, 0)`.
+  pub fn tile(&self, p: Point) -> Point {
+    Point::new(
+      p.x.div_euclid(self.grid.width() as isize),
+      p.y.div_euclid(self.grid.height() as isize),
+    )
+  }
+
+  pub fn get(&self, p: Point) -> &'a T {
+    &self.grid[self.wrap(p)]
+  }
+
+  /// Orthogonal neighbours of `p`, which always exist on a tiled plane.
+  pub fn neighbours4(&self, p: Point) -> impl Iterator<Item = Point> {
+    Direction::ALL.into_iter().map(move |d| p + d.delta())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn wraps_negative_points() {
+    let grid: Grid<char> = "abc\ndef".parse().unwrap();
+    let tiled = grid.tiled().unwrap();
+    assert_eq!(tiled.wrap(Point::new(-1, -1)), Point::new(2, 1));
+    assert_eq!(tiled.wrap(Point::new(-3, -2)), Point::new(0, 0));
+    assert_eq!(tiled.wrap(Point::new(-4, 5)), Point::new(2, 1));
+    assert_eq!(tiled.tile(Point::new(-1, -1)), Point::new(-1, -1));
+    assert_eq!(tiled.tile(Point::new(-3, -2)), Point::new(-1, -1));
+    assert_eq!(tiled.tile(Point::new(3, 1)), Point::new(1, 0));
+    assert_eq!(*tiled.get(Point::new(-1, 0)), 'c');
+    assert_eq!(*tiled.get(Point::new(7, -3)), 'e');
+    assert_eq!(tiled.neighbours4(Point::new(0, 0)).count(), 4);
+  }
+
+  #[test]
+  fn empty_grids_do_not_tile() {
+    assert!(Grid::<char>::new(0, 0, '.').tiled().is_none());
+    assert!(Grid::new(3, 0, '.').tiled().is_none());
+    assert!(Grid::new(1, 1, '.').tiled().is_some());
+  }
+}
@@ -0,0 +1,24 @@
+use std::borrow::Cow;
+
+fn is_normalized(input: &str) -> bool {
+  input.ends_with('\n')
+    && !input.contains('\r')
+    && !input.contains(" \n")
+    && !input.contains("\t\n")
+}
+
+/// Strips a leading byte order mark, converts CRLF line endings, trims
+/// trailing whitespace from every line and makes sure the input ends with a
+/// line break. Empty input stays empty.
+pub fn normalize(input: &str) -> Cow<'_, str> {
+  let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+  if input.is_empty() || is_normalized(input) {
+    return Cow::Borrowed(input);
+  }
+  let mut normalized = String::with_capacity(input.len() + 1);
+  for line in input.lines() {
+    normalized.push_str(line.trim_end_matches([' ', '\t', '\r']));
+    normalized.push('\n');
+  }
+  Cow::Owned(normalized)
+}
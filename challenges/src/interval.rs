@@ -0,0 +1,361 @@
+use std::{cmp::Ordering, fmt::Debug, ops::Range};
+
+/// Set of integers stored as sorted, disjoint and non-adjacent half-open
+/// ranges.
+#[derive(Clone, PartialEq, Eq, Hash, Default)]
+pub struct IntervalSet {
+  ranges: Vec<Range<i64>>,
+}
+
+impl IntervalSet {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  fn normalized(mut ranges: Vec<Range<i64>>) -> Self {
+    ranges.retain(|r| r.start < r.end);
+    ranges.sort_unstable_by_key(|r| r.start);
+    let mut merged: Vec<Range<i64>> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+      match merged.last_mut() {
+        Some(last) if range.start <= last.end => {
+          last.end = last.end.max(range.end)
+        }
+        _ => merged.push(range),
+      }
+    }
+    IntervalSet { ranges: merged }
+  }
+
+  pub fn ranges(&self) -> &[Range<i64>] {
+    &self.ranges
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.ranges.is_empty()
+  }
+
+  /// Number of integers in the set.
+  pub fn total_len(&self) -> u64 {
+    self.ranges.iter().map(|r| r.end.abs_diff(r.start)).sum()
+  }
+
+  pub fn min(&self) -> Option<i64> {
+    self.ranges.first().map(|r| r.start)
+  }
+
+  pub fn max(&self) -> Option<i64> {
+    self.ranges.last().map(|r| r.end - 1)
+  }
+
+  pub fn contains(&self, value: i64) -> bool {
+    self
+      .ranges
+      .binary_search_by(|r| {
+        if r.end <= value {
+          Ordering::Less
+        } else if r.start > value {
+          Ordering::Greater
+        } else {
+          Ordering::Equal
+        }
+      })
+      .is_ok()
+  }
+
+  pub fn iter(&self) -> impl Iterator<Item = i64> + '_ {
+    self.ranges.iter().flat_map(|r| r.clone())
+  }
+
+  pub fn insert(&mut self, range: Range<i64>) {
+    *self = self.union(&IntervalSet::from(range));
+  }
+
+  pub fn union(&self, other: &Self) -> Self {
+    Self::normalized(
+      self
+        .ranges
+        .iter()
+        .chain(other.ranges.iter())
+        .cloned()
+        .collect(),
+    )
+  }
+
+  pub fn intersection(&self, other: &Self) -> Self {
+    let mut ranges = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < self.ranges.len() && j < other.ranges.len() {
+      let (a, b) = (&self.ranges[i], &other.ranges[j]);
+      let start = a.start.max(b.start);
+      let end = a.end.min(b.end);
+      if start < end {
+        ranges.push(start..end);
+      }
+      if a.end < b.end {
+        i += 1;
+      } else {
+        j += 1;
+      }
+    }
+    IntervalSet { ranges }
+  }
+
+  pub fn difference(&self, other: &Self) -> Self {
+    let mut ranges = Vec::new();
+    let mut j = 0;
+    for range in &self.ranges {
+      let mut start = range.start;
+      // Skip what ends before this range, the rest may still cut later ones
+      while j < other.ranges.len() && other.ranges[j].end <= start {
+        j += 1;
+      }
+      let mut k = j;
+      while k < other.ranges.len() && other.ranges[k].start < range.end {
+        let cut = &other.ranges[k];
+        if cut.start > start {
+          ranges.push(start..cut.start);
+        }
+        start = start.max(cut.end);
+        k += 1;
+      }
+      if start < range.end {
+        ranges.push(start..range.end);
+      }
+    }
+    IntervalSet { ranges }
+  }
+
+  /// Splits into the values below `at` and the values from `at` upwards.
+  pub fn split_at(&self, at: i64) -> (Self, Self) {
+    let mut below = Vec::new();
+    let mut above = Vec::new();
+    for range in &self.ranges {
+      if range.end <= at {
+        below.push(range.clone());
+      } else if range.start >= at {
+        above.push(range.clone());
+      } else {
+        below.push(range.start..at);
+        above.push(at..range.end);
+      }
+    }
+    (IntervalSet { ranges: below }, IntervalSet { ranges: above })
+  }
+
+  pub fn shift(&self, offset: i64) -> Self {
+    IntervalSet {
+      ranges: self
+        .ranges
+        .iter()
+        .map(|r| r.start + offset..r.end + offset)
+        .collect(),
+    }
+  }
+}
+
+impl From<Range<i64>> for IntervalSet {
+  fn from(range: Range<i64>) -> Self {
+    Self::normalized(vec![range])
+  }
+}
+
+impl FromIterator<Range<i64>> for IntervalSet {
+  fn from_iter<I: IntoIterator<Item = Range<i64>>>(iter: I) -> Self {
+    Self::normalized(iter.into_iter().collect())
+  }
+}
+
+impl Debug for IntervalSet {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_set().entries(self.ranges.iter()).finish()
+  }
+}
+
+/// Piecewise translation of integers: values inside a piece are moved by its
+/// offset, values outside every piece map to themselves.
+#[derive(Clone, PartialEq, Eq, Default)]
+pub struct IntervalMap {
+  pieces: Vec<(Range<i64>, i64)>,
+}
+
+impl IntervalMap {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn pieces(&self) -> &[(Range<i64>, i64)] {
+    &self.pieces
+  }
+
+  /// Values already covered by an earlier piece keep that piece's offset.
+  pub fn insert(&mut self, range: Range<i64>, offset: i64) {
+    let uncovered = IntervalSet::from(range).difference(&self.domain());
+    self
+      .pieces
+      .extend(uncovered.ranges.into_iter().map(|range| (range, offset)));
+    self.pieces.sort_unstable_by_key(|(range, _)| range.start);
+  }
+
+  /// Values moved by some piece, even if by a zero offset.
+  pub fn domain(&self) -> IntervalSet {
+    self.pieces.iter().map(|(range, _)| range.clone()).collect()
+  }
+
+  pub fn map(&self, value: i64) -> i64 {
+    let i = self.pieces.partition_point(|(range, _)| range.end <= value);
+    match self.pieces.get(i) {
+      Some((range, offset)) if range.contains(&value) => value + offset,
+      _ => value,
+    }
+  }
+
+  pub fn map_set(&self, set: &IntervalSet) -> IntervalSet {
+    let unmapped = set.difference(&self.domain());
+    let mapped = self.pieces.iter().flat_map(|(range, offset)| {
+      set
+        .intersection(&IntervalSet::from(range.clone()))
+        .shift(*offset)
+        .ranges
+    });
+    mapped.chain(unmapped.ranges).collect()
+  }
+}
+
+impl FromIterator<(Range<i64>, i64)> for IntervalMap {
+  fn from_iter<I: IntoIterator<Item = (Range<i64>, i64)>>(iter: I) -> Self {
+    iter
+      .into_iter()
+      .fold(IntervalMap::new(), |mut map, (range, offset)| {
+        map.insert(range, offset);
+        map
+      })
+  }
+}
+
+impl Debug for IntervalMap {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_map()
+      .entries(self.pieces.iter().map(|(range, offset)| (range, offset)))
+      .finish()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::collections::BTreeSet;
+
+  use super::*;
+  use crate::differential::{check, Rng};
+
+  type Model = BTreeSet<i64>;
+
+  fn ranges(rng: &mut Rng) -> Vec<Range<i64>> {
+    (0..rng.range(0..5))
+      .map(|_| {
+        let start = rng.range(-20..20);
+        // Some ranges come out empty or reversed
+        start..start + rng.range(-2..8)
+      })
+      .collect()
+  }
+
+  fn model(ranges: &[Range<i64>]) -> Model {
+    ranges.iter().flat_map(|r| r.clone()).collect()
+  }
+
+  /// The values of the set, `None` if its ranges are not sorted, disjoint,
+  /// non-adjacent and non-empty.
+  fn values(set: &IntervalSet) -> Option<Model> {
+    let canonical = set.ranges.iter().all(|r| r.start < r.end)
+      && set.ranges.windows(2).all(|w| w[0].end < w[1].start);
+    canonical.then(|| set.iter().collect())
+  }
+
+  #[derive(Debug, PartialEq)]
+  struct Results {
+    union: Option<Model>,
+    intersection: Option<Model>,
+    difference: Option<Model>,
+    split: (Option<Model>, Option<Model>),
+    shifted: Option<Model>,
+    total_len: u64,
+    contained: Model,
+  }
+
+  type Case = (Vec<Range<i64>>, Vec<Range<i64>>, i64, i64);
+
+  fn generate(rng: &mut Rng) -> Case {
+    (
+      ranges(rng),
+      ranges(rng),
+      rng.range(-25..30),
+      rng.range(-10..10),
+    )
+  }
+
+  fn fast((a, b, at, offset): &Case) -> Results {
+    let (a, b): (IntervalSet, IntervalSet) =
+      (a.iter().cloned().collect(), b.iter().cloned().collect());
+    let (below, above) = a.split_at(*at);
+    Results {
+      union: values(&a.union(&b)),
+      intersection: values(&a.intersection(&b)),
+      difference: values(&a.difference(&b)),
+      split: (values(&below), values(&above)),
+      shifted: values(&a.shift(*offset)),
+      total_len: a.total_len(),
+      contained: (-25..30).filter(|&v| a.contains(v)).collect(),
+    }
+  }
+
+  fn naive((a, b, at, offset): &Case) -> Results {
+    let (a, b) = (model(a), model(b));
+    Results {
+      union: Some(a.union(&b).copied().collect()),
+      intersection: Some(a.intersection(&b).copied().collect()),
+      difference: Some(a.difference(&b).copied().collect()),
+      split: (
+        Some(a.iter().copied().filter(|v| v < at).collect()),
+        Some(a.iter().copied().filter(|v| v >= at).collect()),
+      ),
+      shifted: Some(a.iter().map(|v| v + offset).collect()),
+      total_len: a.len() as u64,
+      contained: a.clone(),
+    }
+  }
+
+  #[test]
+  fn set_matches_model() {
+    check(31, 2000, generate, fast, naive);
+  }
+
+  type MapCase = (Vec<(Range<i64>, i64)>, Vec<Range<i64>>);
+
+  #[test]
+  fn map_matches_model() {
+    let generate = |rng: &mut Rng| {
+      let pieces: Vec<(Range<i64>, i64)> = ranges(rng)
+        .into_iter()
+        .map(|r| (r, *rng.pick(&[-7, 0, 3, 12])))
+        .collect();
+      (pieces, ranges(rng))
+    };
+    let fast = |(pieces, set): &MapCase| {
+      let map: IntervalMap = pieces.iter().cloned().collect();
+      let set: IntervalSet = set.iter().cloned().collect();
+      let each: Model = set.iter().map(|v| map.map(v)).collect();
+      (values(&map.map_set(&set)), each)
+    };
+    let naive = |(pieces, set): &MapCase| {
+      // The first piece inserted over a value decides its offset
+      let map = |v: i64| {
+        let piece = pieces.iter().find(|(r, _)| r.contains(&v));
+        v + piece.map_or(0, |(_, offset)| *offset)
+      };
+      let mapped: Model = model(set).into_iter().map(map).collect();
+      (Some(mapped.clone()), mapped)
+    };
+    check(32, 2000, generate, fast, naive);
+  }
+}
@@ -3,29 +3,92 @@
 #![allow(unused_variables)]
 
 mod _2023;
-mod common;
+mod challenge;
+#[cfg(test)]
+mod differential;
+mod error;
+mod geom;
+mod graph;
+mod grid;
+mod input;
+mod interval;
+mod math;
+mod params;
 mod parser;
+mod pipes;
+mod sequence;
 
-pub fn run(input: &str, challenge: &str) -> i64 {
-  match challenge {
-    "2023/1/1" => _2023::d01::part_1(input),
-    "2023/1/2" => _2023::d01::part_2(input),
-    "2023/3/1" => _2023::d03::part_1(input),
-    "2023/3/2" => _2023::d03::part_2(input),
-    "2023/4/1" => _2023::d04::part_1(input),
-    "2023/4/2" => _2023::d04::part_2(input),
-    "2023/5/1" => _2023::d05::part_1(input),
-    "2023/5/2" => _2023::d05::part_2(input),
-    "2023/7/1" => _2023::d07::part_1(input),
-    "2023/7/2" => _2023::d07::part_2(input),
-    "2023/8/1" => _2023::d08::part_1(input),
-    "2023/8/2" => _2023::d08::part_2(input),
-    "2023/9/1" => _2023::d09::part_1(input),
-    "2023/9/2" => _2023::d09::part_2(input),
-    "2023/10/1" => _2023::d10::part_1(input),
-    "2023/10/2" => _2023::d10::part_2(input),
-    "2023/11/1" => _2023::d11::part_1(input),
-    "2023/11/2" => _2023::d11::part_2(input),
-    _ => panic!("Bad challenge path"),
+use std::borrow::Cow;
+
+pub use challenge::{
+  Challenge, ChallengeError, Day, Part, Solver, Year, YearSolvers,
+};
+pub use error::SolveError;
+pub use input::normalize;
+pub use params::{Param, ParamError, ParamSpec, Params};
+
+/// Every year with solutions, oldest first.
+pub const YEARS: &[YearSolvers] = &[_2023::SOLVERS];
+
+pub struct RunOptions {
+  /// Pass the input through [`normalize`] before solving.
+  pub normalize: bool,
+  /// Overrides of the day's parameters.
+  pub params: Params,
+}
+
+impl Default for RunOptions {
+  fn default() -> Self {
+    RunOptions {
+      normalize: true,
+      params: Params::new(),
+    }
+  }
+}
+
+pub fn solver(challenge: Challenge) -> Option<Solver> {
+  YEARS
+    .iter()
+    .find(|y| y.year() == challenge.year)?
+    .solver(challenge.day, challenge.part)
+}
+
+/// Parameters the challenge's day reads, none for unsolved days.
+pub fn params(challenge: Challenge) -> &'static [&'static dyn ParamSpec] {
+  YEARS
+    .iter()
+    .find(|y| y.year() == challenge.year)
+    .map_or(&[], |y| y.params(challenge.day))
+}
+
+/// Every registered challenge, in order.
+pub fn challenges() -> impl Iterator<Item = Challenge> {
+  YEARS.iter().flat_map(|y| y.challenges())
+}
+
+pub fn run(input: &str, challenge: Challenge) -> Result<i64, SolveError> {
+  run_with(input, challenge, &RunOptions::default())
+}
+
+pub fn run_with(
+  input: &str,
+  challenge: Challenge,
+  options: &RunOptions,
+) -> Result<i64, SolveError> {
+  let solve = solver(challenge).ok_or(SolveError::Unsolved(challenge))?;
+  let specs = params(challenge);
+  for (name, value) in options.params.iter() {
+    let spec = specs.iter().find(|s| s.name() == name).ok_or_else(|| {
+      ParamError::Unknown {
+        name: name.into(),
+        challenge,
+      }
+    })?;
+    spec.check(value)?;
   }
+  let input = match options.normalize {
+    true => normalize(input),
+    false => Cow::Borrowed(input),
+  };
+  solve.solve(&input, &options.params)
 }
@@ -0,0 +1,251 @@
+//! Number theory on `i64`. Intermediate products are done in `i128`, so only
+//! results that do not fit `i64` fail.
+
+pub fn gcd(a: i64, b: i64) -> i64 {
+  let (mut a, mut b) = (a.unsigned_abs(), b.unsigned_abs());
+  while b != 0 {
+    (a, b) = (b, a % b);
+  }
+  a as i64
+}
+
+/// Non-negative least common multiple, `None` on overflow. The lcm with zero
+/// is zero.
+pub fn checked_lcm(a: i64, b: i64) -> Option<i64> {
+  if a == 0 || b == 0 {
+    return Some(0);
+  }
+  (a / gcd(a, b)).checked_mul(b)?.checked_abs()
+}
+
+/// Least common multiple of all the values, 1 for none.
+pub fn lcm_all(values: impl IntoIterator<Item = i64>) -> Option<i64> {
+  values.into_iter().try_fold(1, checked_lcm)
+}
+
+/// `(g, x, y)` with `a * x + b * y == g`, where `g` is the non-negative gcd.
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+  let (mut r0, mut r1) = (a as i128, b as i128);
+  let (mut x0, mut x1) = (1i128, 0i128);
+  let (mut y0, mut y1) = (0i128, 1i128);
+  while r1 != 0 {
+    let q = r0 / r1;
+    (r0, r1) = (r1, r0 - q * r1);
+    (x0, x1) = (x1, x0 - q * x1);
+    (y0, y1) = (y1, y0 - q * y1);
+  }
+  if r0 < 0 {
+    (r0, x0, y0) = (-r0, -x0, -y0);
+  }
+  (r0 as i64, x0 as i64, y0 as i64)
+}
+
+/// `x` in `0..m` with `a * x ≡ 1 (mod m)`, `None` if `a` and `m` are not
+/// coprime. `m` must be positive.
+pub fn mod_inverse(a: i64, m: i64) -> Option<i64> {
+  assert!(m > 0, "modulus must be positive");
+  let (g, x, _) = extended_gcd(a.rem_euclid(m), m);
+  (g == 1).then(|| x.rem_euclid(m))
+}
+
+/// `base^exp mod m` in `0..m`. `m` must be positive.
+pub fn mod_pow(base: i64, mut exp: u64, m: i64) -> i64 {
+  assert!(m > 0, "modulus must be positive");
+  let m = m as i128;
+  let mut base = (base as i128).rem_euclid(m);
+  let mut result = 1 % m;
+  while exp > 0 {
+    if exp & 1 == 1 {
+      result = result * base % m;
+    }
+    base = base * base % m;
+    exp >>= 1;
+  }
+  result as i64
+}
+
+/// Solves `x ≡ r (mod m)` for every `(r, m)` pair, the moduli need not be
+/// coprime. Returns the smallest non-negative solution and the lcm of the
+/// moduli, which all solutions are congruent modulo.
+///
+/// `None` if the congruences contradict each other, a modulus is not
+/// positive or the lcm overflows.
+pub fn crt(
+  congruences: impl IntoIterator<Item = (i64, i64)>,
+) -> Option<(i64, i64)> {
+  congruences
+    .into_iter()
+    .try_fold((0, 1), |(r1, m1), (r2, m2)| {
+      if m2 <= 0 {
+        return None;
+      }
+      let (g, p, _) = extended_gcd(m1, m2);
+      let diff = r2 as i128 - r1 as i128;
+      if diff % g as i128 != 0 {
+        return None;
+      }
+      let lcm = checked_lcm(m1, m2)? as i128;
+      // m1 * k ≡ diff (mod m2), with p inverting m1 / g modulo m2 / g
+      let step = (m2 / g) as i128;
+      let k = (diff / g as i128 % step * p as i128).rem_euclid(step);
+      let x = (r1 as i128 + m1 as i128 * k).rem_euclid(lcm);
+      Some((x as i64, lcm as i64))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const SMALL: std::ops::RangeInclusive<i64> = -40..=40;
+
+  #[test]
+  fn gcd_and_lcm() {
+    for a in SMALL {
+      for b in SMALL {
+        let g = gcd(a, b);
+        assert_eq!(g, num::Integer::gcd(&a, &b), "gcd({}, {})", a, b);
+        let lcm = checked_lcm(a, b).unwrap();
+        assert_eq!(lcm, num::Integer::lcm(&a, &b), "lcm({}, {})", a, b);
+        if g != 0 {
+          assert_eq!(lcm * g, (a * b).abs());
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn lcm_overflow() {
+    assert_eq!(checked_lcm(i64::MAX, i64::MAX), Some(i64::MAX));
+    assert_eq!(checked_lcm(i64::MAX, 2), None);
+    assert_eq!(checked_lcm(i64::MIN, 1), None);
+    assert_eq!(checked_lcm(1 << 40, 3 << 40), Some(3 << 40));
+    assert_eq!(lcm_all([]), Some(1));
+    assert_eq!(lcm_all([4, 6, 10]), Some(60));
+    assert_eq!(lcm_all([1 << 62, 1 << 40, 3]), None);
+    let primes = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47];
+    assert_eq!(lcm_all(primes), Some(614_889_782_588_491_410));
+    assert_eq!(lcm_all(primes.into_iter().chain([53])), None);
+  }
+
+  #[test]
+  fn extended_gcd_identity() {
+    for a in SMALL {
+      for b in SMALL {
+        let (g, x, y) = extended_gcd(a, b);
+        assert_eq!(g, gcd(a, b), "gcd of ({}, {})", a, b);
+        assert_eq!(a * x + b * y, g, "identity for ({}, {})", a, b);
+      }
+    }
+    let (g, x, y) = extended_gcd(i64::MAX, i64::MAX - 1);
+    assert_eq!(g, 1);
+    assert_eq!(
+      i64::MAX as i128 * x as i128 + (i64::MAX - 1) as i128 * y as i128,
+      1
+    );
+  }
+
+  #[test]
+  fn inverse_matches_search() {
+    for m in 1..=60i64 {
+      for a in -60..=60 {
+        let expected = (0..m).find(|x| (a * x).rem_euclid(m) == 1 % m);
+        assert_eq!(mod_inverse(a, m), expected, "{}^-1 mod {}", a, m);
+      }
+    }
+    let m = 1_000_000_007;
+    assert_eq!(mod_inverse(2, m), Some(500_000_004));
+  }
+
+  #[test]
+  fn pow_matches_repeated_multiplication() {
+    for m in 1..=30 {
+      for base in -30..=30 {
+        let mut expected = 1 % m;
+        for exp in 0..=40 {
+          assert_eq!(
+            mod_pow(base, exp, m),
+            expected,
+            "{}^{} mod {}",
+            base,
+            exp,
+            m
+          );
+          expected = (expected * base).rem_euclid(m);
+        }
+      }
+    }
+    let m = 1_000_000_007;
+    // Fermat: a^(p-1) ≡ 1 for prime p
+    assert_eq!(mod_pow(123_456_789, m as u64 - 1, m), 1);
+    assert_eq!(mod_pow(i64::MAX, u64::MAX, i64::MAX - 1), 1);
+  }
+
+  /// Smallest non-negative solution by trying every candidate below the lcm.
+  fn crt_search(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    let lcm = lcm_all(congruences.iter().map(|&(_, m)| m))?;
+    (0..lcm)
+      .find(|x| congruences.iter().all(|&(r, m)| (x - r).rem_euclid(m) == 0))
+      .map(|x| (x, lcm))
+  }
+
+  #[test]
+  fn crt_pairs_match_search() {
+    for m1 in 1..=12 {
+      for m2 in 1..=12 {
+        for r1 in -m1..2 * m1 {
+          for r2 in -m2..2 * m2 {
+            let congruences = [(r1, m1), (r2, m2)];
+            assert_eq!(
+              crt(congruences),
+              crt_search(&congruences),
+              "{:?}",
+              congruences
+            );
+          }
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn crt_triples_match_search() {
+    for m1 in 1..=6 {
+      for m2 in 1..=6 {
+        for m3 in 1..=6 {
+          for r1 in 0..m1 {
+            for r2 in 0..m2 {
+              for r3 in 0..m3 {
+                let congruences = [(r1, m1), (r2, m2), (r3, m3)];
+                assert_eq!(
+                  crt(congruences),
+                  crt_search(&congruences),
+                  "{:?}",
+                  congruences
+                );
+              }
+            }
+          }
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn crt_edge_cases() {
+    assert_eq!(crt([]), Some((0, 1)));
+    assert_eq!(crt([(5, 0)]), None);
+    assert_eq!(crt([(5, -3)]), None);
+    assert_eq!(crt([(1, 4), (2, 6)]), None);
+    assert_eq!(crt([(3, 4), (1, 6)]), Some((7, 12)));
+    // Large coprime moduli whose product only fits thanks to i128 steps
+    let (p, q) = (1_000_000_007, 998_244_353);
+    let (x, lcm) = crt([(123, p), (456, q)]).unwrap();
+    assert_eq!(lcm, p * q);
+    assert_eq!((x % p, x % q), (123, 456));
+    let (m1, m2) = (1 << 30, (1 << 30) - 1);
+    let lcm = crt([(0, m1), (1, m2), (0, 1 << 20)]).map(|(_, lcm)| lcm);
+    assert_eq!(lcm, Some(m1 * m2));
+    assert_eq!(crt([(0, m1), (1, m2), (0, 5 << 32)]), None);
+  }
+}
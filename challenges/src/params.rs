@@ -0,0 +1,195 @@
+//! Named solver parameters, for constants that differ between the examples
+//! and the real input, like an expansion factor.
+
+use std::{collections::BTreeMap, fmt::Display, str::FromStr};
+
+use crate::challenge::Challenge;
+
+/// Parameter values overriding the defaults, as given on the command line or
+/// next to an example's expected answer. Values are parsed by the solver.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params {
+  values: BTreeMap<String, String>,
+}
+
+impl Params {
+  pub fn new() -> Self {
+    Params::default()
+  }
+
+  pub fn set(&mut self, name: impl Into<String>, value: impl Into<String>) {
+    self.values.insert(name.into(), value.into());
+  }
+
+  pub fn get(&self, name: &str) -> Option<&str> {
+    self.values.get(name).map(String::as_str)
+  }
+
+  pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+    self.values.iter().map(|(n, v)| (n.as_str(), v.as_str()))
+  }
+
+  /// Splits a `name=value` assignment.
+  pub fn assignment(s: &str) -> Result<(String, String), ParamError> {
+    match s.split_once('=') {
+      Some((name, value)) if !name.is_empty() => {
+        Ok((name.into(), value.into()))
+      }
+      _ => Err(ParamError::Assignment(s.into())),
+    }
+  }
+}
+
+impl FromIterator<(String, String)> for Params {
+  fn from_iter<I: IntoIterator<Item = (String, String)>>(iter: I) -> Self {
+    Params {
+      values: iter.into_iter().collect(),
+    }
+  }
+}
+
+/// A parameter a solver reads, with the value for the real input.
+pub struct Param<T> {
+  name: &'static str,
+  default: T,
+  valid: fn(&T) -> bool,
+}
+
+impl<T> Param<T> {
+  pub const fn new(name: &'static str, default: T) -> Self {
+    Param::checked(name, default, |_| true)
+  }
+
+  /// A parameter whose overrides are rejected unless `valid` accepts them.
+  pub const fn checked(
+    name: &'static str,
+    default: T,
+    valid: fn(&T) -> bool,
+  ) -> Self {
+    Param {
+      name,
+      default,
+      valid,
+    }
+  }
+}
+
+impl<T: FromStr + Clone> Param<T> {
+  /// The overridden value if there is one, else the default.
+  pub fn get(&self, params: &Params) -> Result<T, ParamError> {
+    match params.get(self.name) {
+      None => Ok(self.default.clone()),
+      Some(value) => match value.parse() {
+        Ok(parsed) if (self.valid)(&parsed) => Ok(parsed),
+        _ => Err(ParamError::Invalid {
+          name: self.name.into(),
+          value: value.into(),
+        }),
+      },
+    }
+  }
+}
+
+/// A parameter of any type, as listed in the solver registry.
+pub trait ParamSpec {
+  fn name(&self) -> &'static str;
+
+  /// Whether the value parses as the parameter's type and is valid.
+  fn check(&self, value: &str) -> Result<(), ParamError>;
+
+  fn default_value(&self) -> String;
+}
+
+impl<T: FromStr + Clone + Display> ParamSpec for Param<T> {
+  fn name(&self) -> &'static str {
+    self.name
+  }
+
+  fn check(&self, value: &str) -> Result<(), ParamError> {
+    let mut params = Params::new();
+    params.set(self.name, value);
+    self.get(&params).map(|_| ())
+  }
+
+  fn default_value(&self) -> String {
+    self.default.to_string()
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParamError {
+  /// Not of the form `name=value`.
+  Assignment(String),
+  Unknown {
+    name: String,
+    challenge: Challenge,
+  },
+  Invalid {
+    name: String,
+    value: String,
+  },
+}
+
+impl Display for ParamError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      ParamError::Assignment(s) => {
+        write!(f, "Expected name=value, e.g. multiplier=10, got {:?}", s)
+      }
+      ParamError::Unknown { name, challenge } => {
+        write!(f, "{} has no parameter {:?}", challenge, name)
+      }
+      ParamError::Invalid { name, value } => {
+        write!(f, "Invalid value {:?} for parameter {:?}", value, name)
+      }
+    }
+  }
+}
+
+impl std::error::Error for ParamError {}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const FACTOR: Param<i64> = Param::new("factor", 7);
+  const SIZE: Param<i64> = Param::checked("size", 3, |&size| size > 0);
+
+  #[test]
+  fn defaults_and_overrides() {
+    let mut params = Params::new();
+    assert_eq!(FACTOR.get(&params), Ok(7));
+    params.set("factor", "-3");
+    assert_eq!(FACTOR.get(&params), Ok(-3));
+    params.set("factor", "x");
+    assert!(FACTOR.get(&params).is_err());
+    assert!(FACTOR.check("12").is_ok());
+    assert_eq!(FACTOR.default_value(), "7");
+  }
+
+  #[test]
+  fn validates_overrides() {
+    let mut params = Params::new();
+    params.set("size", "1");
+    assert_eq!(SIZE.get(&params), Ok(1));
+    for value in ["0", "-5", "x"] {
+      params.set("size", value);
+      let invalid = ParamError::Invalid {
+        name: "size".into(),
+        value: value.into(),
+      };
+      assert_eq!(SIZE.get(&params), Err(invalid.clone()));
+      assert_eq!(SIZE.check(value), Err(invalid));
+    }
+  }
+
+  #[test]
+  fn assignments() {
+    let pair = |name: &str, value: &str| Ok((name.into(), value.into()));
+    assert_eq!(Params::assignment("a=1"), pair("a", "1"));
+    assert_eq!(Params::assignment("a=b=c"), pair("a", "b=c"));
+    assert_eq!(Params::assignment("a="), pair("a", ""));
+    assert!(Params::assignment("=1").is_err());
+    assert!(Params::assignment("a").is_err());
+  }
+}
@@ -1,10 +1,23 @@
+use std::str::FromStr;
+
 use nom::{
-  character::complete::{multispace0, space0},
-  error::ParseError,
-  sequence::delimited,
-  IResult,
+  branch::alt,
+  bytes::complete::tag,
+  character::complete::{
+    char, digit1, line_ending, multispace0, space0, space1,
+  },
+  combinator::{all_consuming, map_res, opt, recognize},
+  error::{FromExternalError, ParseError, VerboseError},
+  multi::{many1, separated_list1},
+  sequence::{delimited, pair, preceded, separated_pair, terminated, tuple},
+  Finish, IResult, Parser,
 };
 
+use crate::error::{self, SolveError};
+
+/// Parser result carrying the context needed for readable error reports.
+pub type PResult<'a, O> = IResult<&'a str, O, VerboseError<&'a str>>;
+
 pub fn ws_line<'a, F, O, E: ParseError<&'a str>>(
   inner: F,
 ) -> impl FnMut(&'a str) -> IResult<&'a str, O, E>
@@ -22,3 +35,203 @@ where
 {
   delimited(multispace0, inner, multispace0)
 }
+
+/// Non-negative integer of any type, e.g. `42`.
+pub fn unsigned<'a, T: FromStr, E>(input: &'a str) -> IResult<&'a str, T, E>
+where
+  E: ParseError<&'a str> + FromExternalError<&'a str, T::Err>,
+{
+  map_res(digit1, str::parse)(input)
+}
+
+/// Integer with an optional sign, e.g. `-42` or `+7`.
+pub fn signed<'a, T: FromStr, E>(input: &'a str) -> IResult<&'a str, T, E>
+where
+  E: ParseError<&'a str> + FromExternalError<&'a str, T::Err>,
+{
+  map_res(
+    recognize(pair(opt(alt((char('-'), char('+')))), digit1)),
+    |s: &str| s.trim_start_matches('+').parse(),
+  )(input)
+}
+
+/// Numbers separated by spaces on a single line, e.g. ` 1 -2  3`.
+pub fn numbers<'a, T: FromStr, E>(input: &'a str) -> IResult<&'a str, Vec<T>, E>
+where
+  E: ParseError<&'a str> + FromExternalError<&'a str, T::Err>,
+{
+  preceded(space0, separated_list1(space1, signed))(input)
+}
+
+/// `key: values` on a single line.
+pub fn key_values<'a, K, V, E, FK, FV>(
+  key: FK,
+  values: FV,
+) -> impl FnMut(&'a str) -> IResult<&'a str, (K, V), E>
+where
+  E: ParseError<&'a str>,
+  FK: Parser<&'a str, K, E>,
+  FV: Parser<&'a str, V, E>,
+{
+  separated_pair(key, ws_line(tag(":")), values)
+}
+
+fn at_blank_line(input: &str) -> bool {
+  let rest = input.trim_start_matches([' ', '\t']);
+  rest.is_empty() || rest.starts_with('\n') || rest.starts_with("\r\n")
+}
+
+/// One item per line, up to a blank line or the end of the input. The line
+/// break after the last item is left in place, so that blocks of lines can
+/// be followed by [`blank_lines`]. Any other line in between must be an
+/// item, so errors point at the offending line rather than the end of the
+/// block.
+pub fn lines<'a, O, E, F>(
+  mut item: F,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>, E>
+where
+  E: ParseError<&'a str>,
+  F: Parser<&'a str, O, E>,
+{
+  move |input| {
+    let (mut input, first) = item.parse(input)?;
+    let mut items = vec![first];
+    loop {
+      let rest = match line_ending::<_, E>(input) {
+        Ok((rest, _)) if !at_blank_line(rest) => rest,
+        _ => return Ok((input, items)),
+      };
+      let (rest, next) = item.parse(rest).map_err(|e| match e {
+        nom::Err::Error(e) => nom::Err::Failure(e),
+        e => e,
+      })?;
+      items.push(next);
+      input = rest;
+    }
+  }
+}
+
+/// Line break followed by at least one empty line.
+pub fn blank_lines<'a, E: ParseError<&'a str>>(
+  input: &'a str,
+) -> IResult<&'a str, (), E> {
+  let (input, _) = pair(line_ending, many1(pair(space0, line_ending)))(input)?;
+  Ok((input, ()))
+}
+
+/// Sections separated by one or more blank lines.
+pub fn sections<'a, O, E, F>(
+  section: F,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>, E>
+where
+  E: ParseError<&'a str>,
+  F: Parser<&'a str, O, E>,
+{
+  separated_list1(blank_lines, section)
+}
+
+/// A `label:` header line followed by its body, like `seed-to-soil map:`.
+pub fn labelled_block<'a, L, O, E, FL, FB>(
+  label: FL,
+  body: FB,
+) -> impl FnMut(&'a str) -> IResult<&'a str, (L, O), E>
+where
+  E: ParseError<&'a str>,
+  FL: Parser<&'a str, L, E>,
+  FB: Parser<&'a str, O, E>,
+{
+  pair(
+    terminated(label, tuple((ws_line(tag(":")), line_ending))),
+    body,
+  )
+}
+
+/// Runs `parser` over the whole input, allowing only trailing whitespace to
+/// remain.
+pub fn parse_all<'a, O, F>(parser: F, input: &'a str) -> Result<O, SolveError>
+where
+  F: Parser<&'a str, O, VerboseError<&'a str>>,
+{
+  all_consuming(terminated(parser, multispace0))(input)
+    .finish()
+    .map(|(_, output)| output)
+    .map_err(|e| error::ParseError::from_verbose(input, e).into())
+}
+
+#[cfg(test)]
+mod tests {
+  use nom::character::complete::alpha1;
+
+  use super::*;
+
+  fn number(input: &str) -> PResult<'_, i64> {
+    signed(input)
+  }
+
+  fn row(input: &str) -> PResult<'_, Vec<i64>> {
+    numbers(input)
+  }
+
+  fn name(input: &str) -> PResult<'_, &str> {
+    alpha1(input)
+  }
+
+  fn failed<O>(result: PResult<'_, O>) -> bool {
+    matches!(result, Err(nom::Err::Failure(_)))
+  }
+
+  #[test]
+  fn signs() {
+    assert_eq!(number("-42"), Ok(("", -42)));
+    assert_eq!(number("+7 rest"), Ok((" rest", 7)));
+    assert_eq!(number("0"), Ok(("", 0)));
+    assert!(number("- 1").is_err());
+    assert!(number("+-1").is_err());
+    assert!(signed::<u8, VerboseError<&str>>("300").is_err());
+    assert!(unsigned::<i64, VerboseError<&str>>("-1").is_err());
+    assert_eq!(row(" 1 -2  +3\n4"), Ok(("\n4", vec![1, -2, 3])));
+  }
+
+  #[test]
+  fn lines_stop_at_blank_line() {
+    let mut numbers = lines(number);
+    assert_eq!(numbers("1\n2\n\n3"), Ok(("\n\n3", vec![1, 2])));
+    assert_eq!(numbers("1\n2\n  \n3"), Ok(("\n  \n3", vec![1, 2])));
+    assert_eq!(numbers("1\r\n2\r\n"), Ok(("\r\n", vec![1, 2])));
+    assert_eq!(numbers("1 x"), Ok((" x", vec![1])));
+    // A bad first line may still be something else, a bad later one is not
+    assert!(matches!(numbers("x\n1"), Err(nom::Err::Error(_))));
+    assert!(failed(numbers("1\nx")));
+    assert!(failed(numbers("1\n2\n x")));
+  }
+
+  #[test]
+  fn blank_lines_may_hold_spaces() {
+    let blank = blank_lines::<VerboseError<&str>>;
+    assert_eq!(blank("\n\nx"), Ok(("x", ())));
+    assert_eq!(blank("\n  \n\t\r\nx"), Ok(("x", ())));
+    assert!(blank("\nx").is_err());
+    assert!(blank("\n  x").is_err());
+    let mut blocks = sections(lines(number));
+    assert_eq!(blocks("1\n2\n \n\n3"), Ok(("", vec![vec![1, 2], vec![3]])));
+  }
+
+  #[test]
+  fn labelled_blocks() {
+    let mut block = labelled_block(name, lines(row));
+    let body = vec![vec![1, 2], vec![-3]];
+    assert_eq!(block("seeds :\n1 2\n-3"), Ok(("", ("seeds", body))));
+    assert!(block("seeds\n1 2").is_err());
+    assert!(block("seeds: 1 2").is_err());
+  }
+
+  #[test]
+  fn parse_all_allows_trailing_whitespace() {
+    let all = |input| parse_all(lines(number), input);
+    assert_eq!(all("1\n2"), Ok(vec![1, 2]));
+    assert_eq!(all("1\n2\n \n\t\n"), Ok(vec![1, 2]));
+    assert!(all(" 1").is_err());
+    assert!(all("1 x").is_err());
+    assert!(all("1\n2\n\n3").is_err());
+  }
+}
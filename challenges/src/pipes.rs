@@ -0,0 +1,259 @@
+//! Pipe mazes: grids of pipe glyphs that each connect two neighbouring
+//! cells, with a start `S` sitting on one loop.
+
+use std::fmt::Display;
+
+use crate::{
+  geom::Direction,
+  grid::{Grid, GridError, Point},
+};
+
+/// Bitmask of the directions a cell connects to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Pipe(u8);
+
+const fn bit(d: Direction) -> u8 {
+  1 << d as u8
+}
+
+const GLYPHS: [(char, Pipe); 7] = [
+  ('.', Pipe(0)),
+  ('|', Pipe(bit(Direction::North) | bit(Direction::South))),
+  ('-', Pipe(bit(Direction::East) | bit(Direction::West))),
+  ('L', Pipe(bit(Direction::North) | bit(Direction::East))),
+  ('J', Pipe(bit(Direction::North) | bit(Direction::West))),
+  ('7', Pipe(bit(Direction::South) | bit(Direction::West))),
+  ('F', Pipe(bit(Direction::East) | bit(Direction::South))),
+];
+
+impl Pipe {
+  pub const NONE: Pipe = Pipe(0);
+
+  pub fn from_glyph(glyph: char) -> Option<Self> {
+    GLYPHS.iter().find(|g| g.0 == glyph).map(|g| g.1)
+  }
+
+  /// The glyph of the pipe, `?` for a mask that has none.
+  pub fn glyph(self) -> char {
+    GLYPHS.iter().find(|g| g.1 == self).map_or('?', |g| g.0)
+  }
+
+  pub fn connects(self, d: Direction) -> bool {
+    self.0 & bit(d) != 0
+  }
+
+  pub fn directions(self) -> impl Iterator<Item = Direction> {
+    Direction::ALL
+      .into_iter()
+      .filter(move |&d| self.connects(d))
+  }
+
+  fn with(self, d: Direction) -> Self {
+    Pipe(self.0 | bit(d))
+  }
+}
+
+impl Display for Pipe {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", self.glyph())
+  }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum MazeError {
+  Grid(GridError),
+  Glyph {
+    glyph: char,
+    at: Point,
+  },
+  NoStart,
+  ManyStarts(Point, Point),
+  /// The start does not connect to exactly two neighbours.
+  StartShape {
+    at: Point,
+    connections: usize,
+  },
+  /// The loop leads from `at` towards a cell that does not connect back.
+  Broken {
+    at: Point,
+    towards: Direction,
+  },
+}
+
+impl Display for MazeError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      MazeError::Grid(e) => e.fmt(f),
+      MazeError::Glyph { glyph, at } => {
+        write!(f, "Unknown pipe {:?} at {:?}", glyph, at)
+      }
+      MazeError::NoStart => write!(f, "No start in the maze"),
+      MazeError::ManyStarts(a, b) => {
+        write!(f, "Two starts in the maze, at {:?} and {:?}", a, b)
+      }
+      MazeError::StartShape { at, connections } => write!(
+        f,
+        "Start at {:?} connects to {} pipes, expected 2",
+        at, connections
+      ),
+      MazeError::Broken { at, towards } => {
+        write!(f, "Loop is open at {:?} towards {:?}", at, towards)
+      }
+    }
+  }
+}
+
+impl std::error::Error for MazeError {}
+
+impl From<GridError> for MazeError {
+  fn from(e: GridError) -> Self {
+    MazeError::Grid(e)
+  }
+}
+
+/// A parsed maze, with the start replaced by the pipe it must be.
+#[derive(Debug)]
+pub struct Maze {
+  pipes: Grid<Pipe>,
+  start: Point,
+}
+
+impl Maze {
+  pub fn parse(input: &str) -> Result<Self, MazeError> {
+    let glyphs = input.parse::<Grid<char>>()?;
+    let mut start = None;
+    let mut pipes = Grid::new(glyphs.width(), glyphs.height(), Pipe::NONE);
+    for (at, &glyph) in glyphs.iter_with_points() {
+      match (glyph, start) {
+        ('S', None) => start = Some(at),
+        ('S', Some(first)) => return Err(MazeError::ManyStarts(first, at)),
+        _ => {
+          let pipe =
+            Pipe::from_glyph(glyph).ok_or(MazeError::Glyph { glyph, at })?;
+          pipes.set(at, pipe);
+        }
+      }
+    }
+    let mut maze = Maze {
+      pipes,
+      start: start.ok_or(MazeError::NoStart)?,
+    };
+    let shape = maze.start_shape();
+    let connections = shape.directions().count();
+    if connections != 2 {
+      let at = maze.start;
+      return Err(MazeError::StartShape { at, connections });
+    }
+    maze.pipes.set(maze.start, shape);
+    Ok(maze)
+  }
+
+  /// Pipe connecting the start to every neighbour that connects back.
+  fn start_shape(&self) -> Pipe {
+    Direction::ALL
+      .into_iter()
+      .filter(|&d| self.pipe(self.start + d.delta()).connects(d.reverse()))
+      .fold(Pipe::NONE, Pipe::with)
+  }
+
+  pub fn start(&self) -> Point {
+    self.start
+  }
+
+  /// The pipe at the point, none outside the maze.
+  pub fn pipe(&self, at: Point) -> Pipe {
+    self.pipes.get(at).copied().unwrap_or_default()
+  }
+
+  pub fn pipes(&self) -> &Grid<Pipe> {
+    &self.pipes
+  }
+
+  /// The loop through the start, in order and starting there. Fails if it
+  /// is not closed.
+  pub fn main_loop(&self) -> Result<Vec<Point>, MazeError> {
+    let mut points = vec![self.start];
+    let mut at = self.start;
+    let mut towards = self.pipe(at).directions().next().unwrap();
+    loop {
+      let next = at + towards.delta();
+      let back = towards.reverse();
+      let pipe = self.pipe(next);
+      if !pipe.connects(back) {
+        return Err(MazeError::Broken { at, towards });
+      }
+      if next == self.start {
+        return Ok(points);
+      }
+      points.push(next);
+      at = next;
+      towards = pipe.directions().find(|&d| d != back).unwrap();
+    }
+  }
+
+  /// The maze with only the pipes of the loop left.
+  pub fn cleaned(&self, main_loop: &[Point]) -> Grid<Pipe> {
+    let mut cleaned = self.pipes.map(|_| Pipe::NONE);
+    for &at in main_loop {
+      cleaned.set(at, self.pipe(at));
+    }
+    cleaned
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const SQUARE: &str = "\
+-L|F7
+7S-7|
+L|7||
+-L-J|
+L|-JF
+";
+
+  #[test]
+  fn glyphs_round_trip() {
+    for glyph in ".|-LJ7F".chars() {
+      assert_eq!(Pipe::from_glyph(glyph).map(Pipe::glyph), Some(glyph));
+    }
+    assert_eq!(Pipe::from_glyph('S'), None);
+    assert_eq!(Pipe::from_glyph('x'), None);
+  }
+
+  #[test]
+  fn loop_in_order() {
+    let maze = Maze::parse(SQUARE).unwrap();
+    assert_eq!(maze.pipe(maze.start()).glyph(), 'F');
+    let points: Vec<_> = [(1, 1), (2, 1), (3, 1), (3, 2), (3, 3), (2, 3)]
+      .into_iter()
+      .chain([(1, 3), (1, 2)])
+      .map(Point::from)
+      .collect();
+    let main_loop = maze.main_loop().unwrap();
+    assert_eq!(main_loop, points);
+    let cleaned = maze.cleaned(&main_loop).to_string();
+    assert_eq!(cleaned, ".....\n.F-7.\n.|.|.\n.L-J.\n.....\n");
+  }
+
+  #[test]
+  fn rejects_broken_mazes() {
+    let err = |input| Maze::parse(input).and_then(|m| m.main_loop());
+    assert_eq!(err("..\n.."), Err(MazeError::NoStart));
+    assert!(matches!(err("S.\n.S"), Err(MazeError::ManyStarts(..))));
+    assert!(matches!(err("S.\n.x"), Err(MazeError::Glyph { .. })));
+    // A dead end next to the start
+    assert_eq!(
+      err("S-\n|."),
+      Err(MazeError::Broken {
+        at: Point::new(1, 0),
+        towards: Direction::East,
+      })
+    );
+    assert!(matches!(
+      err("-S-\n.|."),
+      Err(MazeError::StartShape { connections: 3, .. })
+    ));
+  }
+}
@@ -0,0 +1,154 @@
+//! Extrapolation of integer sequences that some polynomial generates, done
+//! exactly on big integers.
+
+use num::{BigInt, Zero};
+
+/// The polynomial through a sequence, in Newton form around its first item:
+/// `f(x) = Σ Δᵏf(0) · C(x, k)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polynomial {
+  /// Forward differences at index 0, up to the degree.
+  differences: Vec<BigInt>,
+  len: usize,
+}
+
+/// Forward differences at index 0 up to the first all-zero level, and
+/// whether the sequence reached one.
+fn newton(values: &[i64]) -> (Vec<BigInt>, bool) {
+  let mut row: Vec<BigInt> = values.iter().map(|&v| v.into()).collect();
+  let mut differences = Vec::new();
+  while !row.is_empty() {
+    if row.iter().all(Zero::is_zero) {
+      return (differences, true);
+    }
+    differences.push(row[0].clone());
+    for i in 1..row.len() {
+      row[i - 1] = &row[i] - &row[i - 1];
+    }
+    row.pop();
+  }
+  (differences, false)
+}
+
+impl Polynomial {
+  /// Fits the sequence, whose items are at indices `0..values.len()`.
+  /// `None` if it is not polynomial within its length, that is, no level of
+  /// differences that still has items is all zero.
+  pub fn fit(values: &[i64]) -> Option<Self> {
+    match newton(values) {
+      (differences, true) => Some(Polynomial {
+        differences,
+        len: values.len(),
+      }),
+      (_, false) => None,
+    }
+  }
+
+  /// The polynomial of degree below `values.len()` through every item. It
+  /// agrees with [`Polynomial::fit`] where that succeeds, and otherwise
+  /// takes the last level of differences as constant, so a single item
+  /// extends to a constant sequence. `None` only for an empty sequence.
+  pub fn interpolate(values: &[i64]) -> Option<Self> {
+    if values.is_empty() {
+      return None;
+    }
+    Some(Polynomial {
+      differences: newton(values).0,
+      len: values.len(),
+    })
+  }
+
+  /// Degree of the polynomial, 0 for the zero sequence.
+  pub fn degree(&self) -> usize {
+    self.differences.len().saturating_sub(1)
+  }
+
+  /// Value at any index, including before and after the fitted items.
+  pub fn at(&self, x: i64) -> BigInt {
+    let x = BigInt::from(x);
+    let mut binomial = BigInt::from(1);
+    let mut value = BigInt::zero();
+    for (k, difference) in self.differences.iter().enumerate() {
+      value += difference * &binomial;
+      // C(x, k + 1) = C(x, k) · (x - k) / (k + 1), always exact
+      binomial = binomial * (&x - k) / (k + 1);
+    }
+    value
+  }
+
+  /// Value `steps` items after the last fitted one.
+  pub fn forward(&self, steps: i64) -> BigInt {
+    self.at(self.len as i64 - 1 + steps)
+  }
+
+  /// Value `steps` items before the first fitted one.
+  pub fn backward(&self, steps: i64) -> BigInt {
+    self.at(-steps)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn cubic(x: i64) -> i64 {
+    2 * x * x * x - 3 * x * x + 7 * x - 11
+  }
+
+  #[test]
+  fn degrees() {
+    assert_eq!(Polynomial::fit(&[0, 0, 0]).map(|p| p.degree()), Some(0));
+    assert_eq!(Polynomial::fit(&[5, 5]).map(|p| p.degree()), Some(0));
+    assert_eq!(Polynomial::fit(&[1, 3, 5]).map(|p| p.degree()), Some(1));
+    let values: Vec<i64> = (0..6).map(cubic).collect();
+    assert_eq!(Polynomial::fit(&values).map(|p| p.degree()), Some(3));
+  }
+
+  #[test]
+  fn not_polynomial_within_length() {
+    assert_eq!(Polynomial::fit(&[]), None);
+    assert_eq!(Polynomial::fit(&[7]), None);
+    assert_eq!(Polynomial::fit(&[1, 2, 4, 8, 16]), None);
+    // A cubic needs five items to be told apart from a quartic
+    let values: Vec<i64> = (0..4).map(cubic).collect();
+    assert_eq!(Polynomial::fit(&values), None);
+  }
+
+  #[test]
+  fn interpolates_short_sequences() {
+    assert_eq!(Polynomial::interpolate(&[]), None);
+    let constant = Polynomial::interpolate(&[7]).unwrap();
+    assert_eq!(
+      (constant.forward(3), constant.backward(2)),
+      (7.into(), 7.into())
+    );
+    // Through 1, 2, 4, 8, 16 the quartic goes on with 31 and comes from 1
+    let p = Polynomial::interpolate(&[1, 2, 4, 8, 16]).unwrap();
+    assert_eq!(p.degree(), 4);
+    assert_eq!((p.forward(1), p.backward(1)), (31.into(), 1.into()));
+    let values: Vec<i64> = (0..5).map(cubic).collect();
+    assert_eq!(Polynomial::interpolate(&values), Polynomial::fit(&values));
+  }
+
+  #[test]
+  fn predicts_both_ways() {
+    let values: Vec<i64> = (0..5).map(cubic).collect();
+    let p = Polynomial::fit(&values).unwrap();
+    for x in -20..20 {
+      assert_eq!(p.at(x), cubic(x).into(), "f({})", x);
+    }
+    assert_eq!(p.forward(1), cubic(5).into());
+    assert_eq!(p.forward(10), cubic(14).into());
+    assert_eq!(p.backward(1), cubic(-1).into());
+    assert_eq!(p.backward(10), cubic(-10).into());
+  }
+
+  #[test]
+  fn exceeds_i64() {
+    let p = Polynomial::fit(&[i64::MAX - 2, i64::MAX - 1, i64::MAX]).unwrap();
+    assert_eq!(p.forward(1), BigInt::from(i64::MAX) + 1);
+    let p = Polynomial::fit(&[0, 1 << 40, 1 << 41, 3 << 40]).unwrap();
+    let expected: BigInt = BigInt::from(1) << 40;
+    assert_eq!(p.at(1 << 30), expected << 30);
+  }
+}
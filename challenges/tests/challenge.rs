@@ -0,0 +1,80 @@
+use challenges::{
+  challenges, run, run_with, Challenge, ChallengeError, ParamError, RunOptions,
+  SolveError,
+};
+
+#[test]
+fn parse_challenge() {
+  let challenge: Challenge = "2023/12/2".parse().unwrap();
+  assert_eq!(challenge.year.get(), 2023);
+  assert_eq!(challenge.day.get(), 12);
+  assert_eq!(challenge.part.get(), 2);
+  assert_eq!(challenge.to_string(), "2023/12/2");
+}
+
+#[test]
+fn reject_bad_challenges() {
+  let err = |s: &str| s.parse::<Challenge>().unwrap_err();
+  assert_eq!(err("2023/1"), ChallengeError::Format("2023/1".into()));
+  assert_eq!(
+    err("2023/1/1/1"),
+    ChallengeError::Format("2023/1/1/1".into())
+  );
+  assert_eq!(err("2014/1/1"), ChallengeError::Year("2014".into()));
+  assert_eq!(err("2023/0/1"), ChallengeError::Day("0".into()));
+  assert_eq!(err("2023/26/1"), ChallengeError::Day("26".into()));
+  assert_eq!(err("2023/x/1"), ChallengeError::Day("x".into()));
+  assert_eq!(err("2023/1/3"), ChallengeError::Part("3".into()));
+}
+
+#[test]
+fn registry() {
+  let registered: Vec<_> = challenges().map(|c| c.to_string()).collect();
+  assert_eq!(registered.first().map(String::as_str), Some("2023/1/1"));
+  assert_eq!(registered.last().map(String::as_str), Some("2023/25/1"));
+  assert!(registered.windows(2).all(|w| w[0] != w[1]));
+
+  let missing: Challenge = "2023/25/2".parse().unwrap();
+  assert_eq!(run("", missing), Err(SolveError::Unsolved(missing)));
+}
+
+#[test]
+fn params() {
+  let challenge: Challenge = "2023/11/2".parse().unwrap();
+  let names: Vec<_> = challenges::params(challenge)
+    .iter()
+    .map(|p| (p.name(), p.default_value()))
+    .collect();
+  assert_eq!(names, [("multiplier", "1000000".to_string())]);
+
+  let input = "#.\n..\n.#\n";
+  let with = |name: &str, value: &str| {
+    let params = [(name.into(), value.into())].into_iter().collect();
+    run_with(
+      input,
+      challenge,
+      &RunOptions {
+        params,
+        ..Default::default()
+      },
+    )
+  };
+  assert_eq!(with("multiplier", "10"), Ok(12));
+  assert_eq!(
+    with("factor", "10"),
+    Err(SolveError::Param(ParamError::Unknown {
+      name: "factor".into(),
+      challenge
+    }))
+  );
+  for value in ["ten", "0", "-5"] {
+    assert_eq!(
+      with("multiplier", value),
+      Err(SolveError::Param(ParamError::Invalid {
+        name: "multiplier".into(),
+        value: value.into()
+      }))
+    );
+  }
+  assert_eq!(with("multiplier", "1"), Ok(3));
+}
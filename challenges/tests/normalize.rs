@@ -0,0 +1,103 @@
+use std::{fs, path::Path};
+
+use challenges::{normalize, run_with, Challenge, Params, RunOptions};
+
+/// An expected answer from a `<day>_<x>.answers` file next to the example
+/// `<day>_<x>.txt`. Each line holds the part and the answer, then any
+/// parameter overrides as `name=value`.
+struct Example {
+  name: String,
+  input: String,
+  challenge: Challenge,
+  expected: i64,
+  params: Params,
+}
+
+fn load_examples() -> Vec<Example> {
+  let mut examples = Vec::new();
+  for dir in fs::read_dir("../input").unwrap() {
+    let dir = dir.unwrap().path();
+    let Some(year) = dir.file_name().and_then(|y| y.to_str()) else {
+      continue;
+    };
+    let mut files: Vec<_> = fs::read_dir(&dir)
+      .unwrap()
+      .map(|f| f.unwrap().path())
+      .filter(|f| f.extension().is_some_and(|e| e == "answers"))
+      .collect();
+    files.sort();
+    for file in files {
+      examples.extend(read_answers(year, &file));
+    }
+  }
+  assert!(!examples.is_empty(), "no .answers files found");
+  examples
+}
+
+fn read_answers(year: &str, file: &Path) -> Vec<Example> {
+  let stem = file.file_stem().unwrap().to_str().unwrap();
+  let day: u8 = stem.split('_').next().unwrap().parse().unwrap();
+  let input = fs::read_to_string(file.with_extension("txt")).unwrap();
+  let answers = fs::read_to_string(file).unwrap();
+  answers
+    .lines()
+    .filter(|line| !line.trim().is_empty())
+    .map(|line| {
+      let mut fields = line.split_whitespace();
+      let part = fields.next().unwrap();
+      let expected = fields.next().unwrap().parse().unwrap();
+      let params = fields.map(|f| Params::assignment(f).unwrap()).collect();
+      Example {
+        name: format!("{}/{}", year, stem),
+        input: input.clone(),
+        challenge: format!("{}/{}/{}", year, day, part).parse().unwrap(),
+        expected,
+        params,
+      }
+    })
+    .collect()
+}
+
+fn check(example: Example, input: &str) {
+  let options = RunOptions {
+    params: example.params,
+    ..RunOptions::default()
+  };
+  assert_eq!(
+    run_with(input, example.challenge, &options),
+    Ok(example.expected),
+    "{} on {} with {:?}",
+    example.challenge,
+    example.name,
+    options.params
+  );
+}
+
+/// BOM, CRLF, trailing whitespace and no final line break, all at once.
+fn mangle(input: &str) -> String {
+  let lines = input.lines().map(|l| format!("{} \t", l));
+  format!("\u{feff}{}", lines.collect::<Vec<_>>().join("\r\n"))
+}
+
+#[test]
+fn normalize_cleans_up() {
+  assert_eq!(normalize("\u{feff}a \r\nb\t\r\n\r\nc"), "a\nb\n\nc\n");
+  assert_eq!(normalize("a\nb\n"), "a\nb\n");
+  assert_eq!(normalize(""), "");
+}
+
+#[test]
+fn examples() {
+  for example in load_examples() {
+    let input = example.input.clone();
+    check(example, &input);
+  }
+}
+
+#[test]
+fn examples_with_messy_input() {
+  for example in load_examples() {
+    let input = mangle(&example.input);
+    check(example, &input);
+  }
+}
@@ -1,23 +1,230 @@
-use clap::Parser;
-use std::{error::Error, path::PathBuf};
+use challenges::{Challenge, Day, Params, Part, Year};
+use clap::{Args, Parser, Subcommand};
+use provider::{HttpFetcher, InputCache, InputProvider, Origin, RateLimiter};
+use std::{
+  error::Error,
+  io::Read,
+  path::{Path, PathBuf},
+  process::ExitCode,
+};
+
+#[cfg(test)]
+mod mock;
+mod provider;
+mod scaffold;
+mod submit;
 
 #[derive(Parser)]
-#[command(version, about)]
+#[command(
+  version,
+  about,
+  args_conflicts_with_subcommands = true,
+  subcommand_negates_reqs = true
+)]
 struct Cli {
-  /// The input file path
-  input: PathBuf,
+  #[command(subcommand)]
+  command: Option<Command>,
+
+  #[command(flatten)]
+  solve: SolveArgs,
+}
+
+#[derive(Subcommand)]
+enum Command {
+  /// Download a puzzle input into the cache, unless it is already there
+  Fetch(FetchArgs),
+  /// Submit an answer, unless earlier verdicts already rule it out
+  Submit(SubmitArgs),
+  /// Generate the module for a new day and register it
+  New(NewArgs),
+}
+
+#[derive(Args)]
+struct SolveArgs {
+  /// The input file path, or "-" to read from stdin
+  #[arg(required_unless_present = "input_text")]
+  input: Option<PathBuf>,
+
+  /// Use this text as the input instead of reading a file
+  #[arg(long, conflicts_with = "input")]
+  input_text: Option<String>,
 
   /// Challenge path to run. Example: "2023/1/1"
-  #[arg(short, long)]
-  challenge: PathBuf,
+  #[arg(short, long, required = true)]
+  challenge: Option<Challenge>,
+
+  /// Pass the input as is, without normalizing line endings and whitespace
+  #[arg(long)]
+  raw: bool,
+
+  /// Override a parameter of the day, e.g. multiplier=10. Repeatable
+  #[arg(long = "param", value_name = "NAME=VALUE", value_parser = Params::assignment)]
+  params: Vec<(String, String)>,
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
-  let cli = Cli::parse();
-  let input = cli.input.to_str().unwrap();
-  let challenge = cli.challenge.to_str().unwrap();
-  let contents = std::fs::read_to_string(input)?;
+#[derive(Args)]
+struct ServerArgs {
+  /// Session cookie of a logged in puzzle site account
+  #[arg(long, env = "AOC_SESSION", hide_env_values = true)]
+  session: String,
+
+  /// Puzzle site to talk to
+  #[arg(long, env = "AOC_BASE_URL", default_value = provider::DEFAULT_BASE_URL)]
+  base_url: String,
+
+  /// Minimum number of seconds between two requests to the site
+  #[arg(long, default_value_t = provider::DEFAULT_INTERVAL.as_secs_f64())]
+  interval: f64,
+}
+
+#[derive(Args)]
+struct PuzzleArgs {
+  #[arg(long)]
+  year: Year,
+
+  /// 1 to 25
+  #[arg(long)]
+  day: Day,
+}
+
+#[derive(Args)]
+struct FetchArgs {
+  #[command(flatten)]
+  puzzle: PuzzleArgs,
+
+  /// Directory the inputs are cached in, as <dir>/<year>/<day>.txt
+  #[arg(long, default_value = "input")]
+  cache_dir: PathBuf,
+
+  #[command(flatten)]
+  server: ServerArgs,
+}
+
+#[derive(Args)]
+struct SubmitArgs {
+  #[command(flatten)]
+  puzzle: PuzzleArgs,
+
+  /// 1 or 2
+  #[arg(long)]
+  part: Part,
 
-  println!("{}", challenges::run(&contents, challenge));
+  /// The answer to submit, solved from the cached input if left out
+  #[arg(long, allow_negative_numbers = true)]
+  answer: Option<i64>,
+
+  /// Directory with the cached inputs and the log of attempts
+  #[arg(long, default_value = "input")]
+  cache_dir: PathBuf,
+
+  #[command(flatten)]
+  server: ServerArgs,
+}
+
+#[derive(Args)]
+struct NewArgs {
+  #[command(flatten)]
+  puzzle: PuzzleArgs,
+
+  /// Workspace root, holding the challenges crate and the inputs
+  #[arg(long, default_value = ".")]
+  root: PathBuf,
+}
+
+impl ServerArgs {
+  fn fetcher(&self, cache_dir: &Path) -> Result<HttpFetcher, Box<dyn Error>> {
+    let interval = std::time::Duration::try_from_secs_f64(self.interval)?;
+    let limiter =
+      RateLimiter::with_stamp(interval, cache_dir.join(".last-request"));
+    Ok(HttpFetcher::new(&self.base_url, &self.session, limiter))
+  }
+}
+
+fn read_input(path: &Path) -> Result<String, Box<dyn Error>> {
+  let mut contents = String::new();
+  let result = if path == Path::new("-") {
+    std::io::stdin().read_to_string(&mut contents)
+  } else {
+    std::fs::File::open(path).and_then(|mut f| f.read_to_string(&mut contents))
+  };
+  match result {
+    Ok(_) => Ok(contents),
+    Err(e) => Err(format!("{}: {}", path.display(), e).into()),
+  }
+}
+
+fn solve(args: SolveArgs) -> Result<(), Box<dyn Error>> {
+  let contents = match (args.input_text, args.input) {
+    (Some(text), _) => text,
+    (None, Some(path)) => read_input(&path)?,
+    (None, None) => unreachable!("clap requires one of the inputs"),
+  };
+  let challenge = args.challenge.expect("clap requires a challenge");
+  let options = challenges::RunOptions {
+    normalize: !args.raw,
+    params: args.params.into_iter().collect(),
+  };
+  println!("{}", challenges::run_with(&contents, challenge, &options)?);
   Ok(())
 }
+
+fn fetch(args: FetchArgs) -> Result<(), Box<dyn Error>> {
+  let fetcher = args.server.fetcher(&args.cache_dir)?;
+  let mut provider =
+    InputProvider::new(InputCache::new(&args.cache_dir), fetcher);
+  let (path, origin) = provider.get(args.puzzle.year, args.puzzle.day)?;
+  if origin == Origin::Cache {
+    eprintln!("Already cached, not downloading again");
+  }
+  println!("{}", path.display());
+  Ok(())
+}
+
+fn submit(args: SubmitArgs) -> Result<(), Box<dyn Error>> {
+  let PuzzleArgs { year, day } = args.puzzle;
+  let challenge = Challenge::new(year, day, args.part);
+  let answer = match args.answer {
+    Some(answer) => answer,
+    None => {
+      let path = InputCache::new(&args.cache_dir).path(year, day);
+      let answer = challenges::run(&read_input(&path)?, challenge)?;
+      eprintln!("Solved {}: {}", challenge, answer);
+      answer
+    }
+  };
+  let mut client = args.server.fetcher(&args.cache_dir)?;
+  let log = submit::AttemptLog::new(&args.cache_dir);
+  let verdict = submit::submit(&log, &mut client, challenge, answer)?;
+  match verdict {
+    submit::Verdict::Correct => {
+      println!("{}", verdict);
+      Ok(())
+    }
+    _ => Err(format!("{} was not accepted: {}", answer, verdict).into()),
+  }
+}
+
+fn new(args: NewArgs) -> Result<(), Box<dyn Error>> {
+  let PuzzleArgs { year, day } = args.puzzle;
+  for path in scaffold::new_day(&args.root, year, day)? {
+    println!("{}", path.display());
+  }
+  Ok(())
+}
+
+fn main() -> ExitCode {
+  let cli = Cli::parse();
+  let result = match cli.command {
+    Some(Command::Fetch(args)) => fetch(args),
+    Some(Command::Submit(args)) => submit(args),
+    Some(Command::New(args)) => new(args),
+    None => solve(cli.solve),
+  };
+  match result {
+    Ok(()) => ExitCode::SUCCESS,
+    Err(e) => {
+      eprintln!("{}", e);
+      ExitCode::FAILURE
+    }
+  }
+}
@@ -0,0 +1,155 @@
+//! Minimal HTTP/1.1 server standing in for the puzzle site in tests, and
+//! scratch directories for the files the commands write.
+
+use std::{
+  io::{BufRead, BufReader, Read, Write},
+  net::{TcpListener, TcpStream},
+  ops::Deref,
+  path::{Path, PathBuf},
+  sync::{Arc, Mutex},
+  thread,
+};
+
+#[derive(Debug, Clone)]
+pub struct Request {
+  pub method: String,
+  pub path: String,
+  pub headers: Vec<(String, String)>,
+  pub body: String,
+}
+
+impl Request {
+  /// Header value by case-insensitive name.
+  pub fn header(&self, name: &str) -> Option<&str> {
+    self
+      .headers
+      .iter()
+      .find(|(key, _)| key.eq_ignore_ascii_case(name))
+      .map(|(_, value)| value.as_str())
+  }
+}
+
+type Handler = dyn Fn(&Request) -> (u16, String) + Send + Sync;
+
+/// Serves every connection with `handler`, recording the requests. The
+/// listener thread is left running until the test process exits.
+pub struct MockServer {
+  port: u16,
+  requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl MockServer {
+  pub fn start(
+    handler: impl Fn(&Request) -> (u16, String) + Send + Sync + 'static,
+  ) -> Self {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&requests);
+    let handler: Arc<Handler> = Arc::new(handler);
+    thread::spawn(move || {
+      for stream in listener.incoming().map_while(Result::ok) {
+        if let Some(request) = read_request(&stream) {
+          let (status, body) = handler(&request);
+          recorded.lock().unwrap().push(request);
+          let _ = write_response(stream, status, &body);
+        }
+      }
+    });
+    MockServer { port, requests }
+  }
+
+  pub fn url(&self) -> String {
+    format!("http://127.0.0.1:{}", self.port)
+  }
+
+  pub fn requests(&self) -> Vec<Request> {
+    self.requests.lock().unwrap().clone()
+  }
+}
+
+fn read_request(stream: &TcpStream) -> Option<Request> {
+  let mut reader = BufReader::new(stream);
+  let mut line = String::new();
+  reader.read_line(&mut line).ok()?;
+  let mut parts = line.split_whitespace();
+  let method = parts.next()?.to_string();
+  let path = parts.next()?.to_string();
+
+  let mut headers = Vec::new();
+  loop {
+    line.clear();
+    reader.read_line(&mut line).ok()?;
+    let header = line.trim_end();
+    if header.is_empty() {
+      break;
+    }
+    let (key, value) = header.split_once(':')?;
+    headers.push((key.trim().to_string(), value.trim().to_string()));
+  }
+
+  let mut request = Request {
+    method,
+    path,
+    headers,
+    body: String::new(),
+  };
+  let len = request
+    .header("content-length")
+    .and_then(|len| len.parse().ok())
+    .unwrap_or(0);
+  let mut body = vec![0; len];
+  reader.read_exact(&mut body).ok()?;
+  request.body = String::from_utf8(body).ok()?;
+  Some(request)
+}
+
+fn write_response(
+  mut stream: TcpStream,
+  status: u16,
+  body: &str,
+) -> std::io::Result<()> {
+  write!(
+    stream,
+    "HTTP/1.1 {} Mock\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+    status,
+    body.len(),
+    body
+  )?;
+  stream.flush()
+}
+
+/// Empty directory unique to the test `name` and this process, removed
+/// again when dropped.
+pub struct TempDir {
+  path: PathBuf,
+}
+
+impl TempDir {
+  pub fn new(name: &str) -> Self {
+    let path =
+      std::env::temp_dir().join(format!("run-{}-{}", name, std::process::id()));
+    // Left over from an earlier run that was killed
+    let _ = std::fs::remove_dir_all(&path);
+    std::fs::create_dir_all(&path).unwrap();
+    TempDir { path }
+  }
+
+  pub fn path(&self) -> &Path {
+    &self.path
+  }
+}
+
+impl Deref for TempDir {
+  type Target = Path;
+
+  fn deref(&self) -> &Path {
+    &self.path
+  }
+}
+
+impl Drop for TempDir {
+  fn drop(&mut self) {
+    let _ = std::fs::remove_dir_all(&self.path);
+  }
+}
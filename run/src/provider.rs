@@ -0,0 +1,323 @@
+use challenges::{Day, Year};
+use std::{
+  fmt::Display,
+  io,
+  path::PathBuf,
+  thread,
+  time::{Duration, SystemTime},
+};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Minimum delay between two requests to the puzzle server.
+pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug)]
+pub enum FetchError {
+  Io(io::Error),
+  Status { code: u16, body: String },
+  Transport(String),
+}
+
+impl Display for FetchError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      FetchError::Io(e) => write!(f, "{}", e),
+      FetchError::Status { code, body } => {
+        write!(f, "Server replied {}: {}", code, body.trim())
+      }
+      FetchError::Transport(e) => write!(f, "Request failed: {}", e),
+    }
+  }
+}
+
+impl std::error::Error for FetchError {}
+
+impl From<io::Error> for FetchError {
+  fn from(e: io::Error) -> Self {
+    FetchError::Io(e)
+  }
+}
+
+impl From<ureq::Error> for FetchError {
+  fn from(e: ureq::Error) -> Self {
+    match e {
+      ureq::Error::Status(code, response) => FetchError::Status {
+        code,
+        body: response.into_string().unwrap_or_default(),
+      },
+      ureq::Error::Transport(e) => FetchError::Transport(e.to_string()),
+    }
+  }
+}
+
+/// Source of puzzle inputs that are not cached yet.
+pub trait Fetcher {
+  fn fetch(&mut self, year: Year, day: Day) -> Result<String, FetchError>;
+}
+
+/// Spaces out calls so that consecutive ones are at least `interval` apart.
+///
+/// With a stamp file the time of the last call is also kept on disk, so the
+/// spacing holds across separate runs of the binary.
+#[derive(Debug)]
+pub struct RateLimiter {
+  interval: Duration,
+  stamp: Option<PathBuf>,
+  last: Option<SystemTime>,
+}
+
+impl RateLimiter {
+  pub fn new(interval: Duration) -> Self {
+    RateLimiter {
+      interval,
+      stamp: None,
+      last: None,
+    }
+  }
+
+  pub fn with_stamp(interval: Duration, stamp: impl Into<PathBuf>) -> Self {
+    RateLimiter {
+      stamp: Some(stamp.into()),
+      ..RateLimiter::new(interval)
+    }
+  }
+
+  fn last(&self) -> Option<SystemTime> {
+    let stamped = self
+      .stamp
+      .as_ref()
+      .and_then(|stamp| std::fs::metadata(stamp).ok()?.modified().ok());
+    self.last.max(stamped)
+  }
+
+  pub fn wait(&mut self) {
+    if let Some(last) = self.last() {
+      // A stamp from the future (clock changes) counts as just now
+      let elapsed = last.elapsed().unwrap_or(Duration::ZERO);
+      if let Some(left) = self.interval.checked_sub(elapsed) {
+        thread::sleep(left);
+      }
+    }
+    self.last = Some(SystemTime::now());
+    if let Some(stamp) = &self.stamp {
+      // Only a courtesy to later runs, failing to write it is not an error
+      let _ = stamp
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|_| std::fs::write(stamp, ""));
+    }
+  }
+}
+
+/// Talks to the puzzle server, authenticated by the session cookie.
+pub struct HttpFetcher {
+  agent: ureq::Agent,
+  base_url: String,
+  session: String,
+  limiter: RateLimiter,
+}
+
+impl HttpFetcher {
+  pub fn new(base_url: &str, session: &str, limiter: RateLimiter) -> Self {
+    HttpFetcher {
+      agent: ureq::AgentBuilder::new()
+        .timeout(Duration::from_secs(30))
+        .user_agent(concat!(
+          env!("CARGO_PKG_NAME"),
+          "/",
+          env!("CARGO_PKG_VERSION")
+        ))
+        .build(),
+      base_url: base_url.trim_end_matches('/').to_string(),
+      session: session.to_string(),
+      limiter,
+    }
+  }
+
+  pub fn get(&mut self, path: &str) -> Result<String, FetchError> {
+    self.limiter.wait();
+    let response = self
+      .agent
+      .get(&format!("{}{}", self.base_url, path))
+      .set("Cookie", &format!("session={}", self.session))
+      .call()?;
+    Ok(response.into_string()?)
+  }
+
+  pub fn post(
+    &mut self,
+    path: &str,
+    form: &[(&str, &str)],
+  ) -> Result<String, FetchError> {
+    self.limiter.wait();
+    let response = self
+      .agent
+      .post(&format!("{}{}", self.base_url, path))
+      .set("Cookie", &format!("session={}", self.session))
+      .send_form(form)?;
+    Ok(response.into_string()?)
+  }
+}
+
+impl Fetcher for HttpFetcher {
+  fn fetch(&mut self, year: Year, day: Day) -> Result<String, FetchError> {
+    self.get(&format!("/{}/day/{}/input", year, day))
+  }
+}
+
+/// Inputs stored on disk as `<dir>/<year>/<day>.txt`, the day zero-padded.
+#[derive(Debug, Clone)]
+pub struct InputCache {
+  dir: PathBuf,
+}
+
+impl InputCache {
+  pub fn new(dir: impl Into<PathBuf>) -> Self {
+    InputCache { dir: dir.into() }
+  }
+
+  pub fn path(&self, year: Year, day: Day) -> PathBuf {
+    self
+      .dir
+      .join(year.to_string())
+      .join(format!("{:02}.txt", day))
+  }
+
+  pub fn contains(&self, year: Year, day: Day) -> bool {
+    self.path(year, day).is_file()
+  }
+
+  /// Writes through a temporary file so an interrupted download never leaves
+  /// a truncated input behind.
+  pub fn store(
+    &self,
+    year: Year,
+    day: Day,
+    contents: &str,
+  ) -> io::Result<PathBuf> {
+    let path = self.path(year, day);
+    std::fs::create_dir_all(path.parent().unwrap())?;
+    let partial = path.with_extension("txt.part");
+    std::fs::write(&partial, contents)?;
+    std::fs::rename(&partial, &path)?;
+    Ok(path)
+  }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Origin {
+  Cache,
+  Downloaded,
+}
+
+/// Cached inputs, falling back to the fetcher for missing ones.
+pub struct InputProvider<F> {
+  cache: InputCache,
+  fetcher: F,
+}
+
+impl<F: Fetcher> InputProvider<F> {
+  pub fn new(cache: InputCache, fetcher: F) -> Self {
+    InputProvider { cache, fetcher }
+  }
+
+  /// Path of the input for `year`/`day`, downloading it if not cached.
+  pub fn get(
+    &mut self,
+    year: Year,
+    day: Day,
+  ) -> Result<(PathBuf, Origin), FetchError> {
+    if self.cache.contains(year, day) {
+      return Ok((self.cache.path(year, day), Origin::Cache));
+    }
+    let contents = self.fetcher.fetch(year, day)?;
+    let path = self.cache.store(year, day, &contents)?;
+    Ok((path, Origin::Downloaded))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::mock::{MockServer, TempDir};
+
+  fn year() -> Year {
+    Year::new(2023).unwrap()
+  }
+
+  fn day(day: u8) -> Day {
+    Day::new(day).unwrap()
+  }
+
+  fn fetcher(server: &MockServer, interval: Duration) -> HttpFetcher {
+    HttpFetcher::new(&server.url(), "secret", RateLimiter::new(interval))
+  }
+
+  #[test]
+  fn downloads_once() {
+    let server = MockServer::start(|_| (200, "1 2 3\n".to_string()));
+    let dir = TempDir::new("downloads-once");
+    let mut provider = InputProvider::new(
+      InputCache::new(dir.path()),
+      fetcher(&server, Duration::ZERO),
+    );
+
+    let (path, origin) = provider.get(year(), day(9)).unwrap();
+    assert_eq!(origin, Origin::Downloaded);
+    assert_eq!(path, dir.join("2023").join("09.txt"));
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "1 2 3\n");
+
+    let (_, origin) = provider.get(year(), day(9)).unwrap();
+    assert_eq!(origin, Origin::Cache);
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "GET");
+    assert_eq!(requests[0].path, "/2023/day/9/input");
+    assert_eq!(requests[0].header("cookie"), Some("session=secret"));
+  }
+
+  #[test]
+  fn failed_download_is_not_cached() {
+    let server = MockServer::start(|_| (400, "Please log in".to_string()));
+    let dir = TempDir::new("failed-download");
+    let mut provider = InputProvider::new(
+      InputCache::new(dir.path()),
+      fetcher(&server, Duration::ZERO),
+    );
+
+    match provider.get(year(), day(1)) {
+      Err(FetchError::Status { code: 400, body }) => {
+        assert_eq!(body, "Please log in")
+      }
+      other => panic!("unexpected {:?}", other),
+    }
+    assert!(!InputCache::new(dir.path()).contains(year(), day(1)));
+    assert!(provider.get(year(), day(1)).is_err());
+    assert_eq!(server.requests().len(), 2);
+  }
+
+  #[test]
+  fn requests_are_spaced_out() {
+    let server = MockServer::start(|_| (200, String::new()));
+    let interval = Duration::from_millis(100);
+    let mut fetcher = fetcher(&server, interval);
+    let start = std::time::Instant::now();
+    fetcher.fetch(year(), day(1)).unwrap();
+    fetcher.fetch(year(), day(2)).unwrap();
+    fetcher.fetch(year(), day(3)).unwrap();
+    assert!(start.elapsed() >= interval * 2);
+  }
+
+  #[test]
+  fn stamp_spaces_out_separate_limiters() {
+    let dir = TempDir::new("stamp");
+    let stamp = dir.join(".last-request");
+    let interval = Duration::from_millis(100);
+    RateLimiter::with_stamp(interval, &stamp).wait();
+    let start = std::time::Instant::now();
+    RateLimiter::with_stamp(interval, &stamp).wait();
+    assert!(start.elapsed() >= interval / 2);
+  }
+}
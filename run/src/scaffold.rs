@@ -0,0 +1,437 @@
+use std::{
+  fmt::Display,
+  io::{self, Write},
+  path::{Path, PathBuf},
+};
+
+use challenges::{Day, Year};
+
+const TEMPLATE: &str = include_str!("../templates/day.rs.template");
+
+const MAX_WIDTH: usize = 80;
+
+#[derive(Debug)]
+pub enum ScaffoldError {
+  /// Something for the day is already there, nothing was written.
+  Exists(String),
+  /// The tree does not look the way the scaffolder expects.
+  Layout(String),
+  Io(io::Error),
+}
+
+impl Display for ScaffoldError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      ScaffoldError::Exists(what) => {
+        write!(f, "Not overwriting existing code: {}", what)
+      }
+      ScaffoldError::Layout(what) => write!(f, "Unexpected layout: {}", what),
+      ScaffoldError::Io(e) => write!(f, "{}", e),
+    }
+  }
+}
+
+impl std::error::Error for ScaffoldError {}
+
+impl From<io::Error> for ScaffoldError {
+  fn from(e: io::Error) -> Self {
+    ScaffoldError::Io(e)
+  }
+}
+
+fn layout(what: &str) -> ScaffoldError {
+  ScaffoldError::Layout(what.to_string())
+}
+
+/// `items` as the body of a `&[...]` literal the way rustfmt lays it out:
+/// on one line if `prefix` followed by the literal fits, one item per line
+/// otherwise.
+fn slice_literal(prefix: &str, items: &[String], indent: &str) -> String {
+  let inline = format!("&[{}]", items.join(", "));
+  if prefix.len() + inline.len() <= MAX_WIDTH && !inline.contains('\n') {
+    return inline;
+  }
+  let lines: String = items
+    .iter()
+    .map(|item| format!("{}  {},\n", indent, item))
+    .collect();
+  format!("&[\n{}{}]", lines, indent)
+}
+
+/// The call `text` starts with, up to its matching parenthesis, and the
+/// arguments inside.
+fn call(text: &str) -> Option<(&str, &str)> {
+  let open = text.find('(')?;
+  let mut depth = 0;
+  for (i, c) in text.char_indices().skip(open) {
+    match c {
+      '(' => depth += 1,
+      ')' if depth == 1 => return Some((&text[..=i], &text[open + 1..i])),
+      ')' => depth -= 1,
+      _ => {}
+    }
+  }
+  None
+}
+
+/// Splits `text` around the first `start` ... `end` span.
+fn span<'a>(
+  text: &'a str,
+  start: &str,
+  end: &str,
+) -> Option<(&'a str, &'a str, &'a str)> {
+  let from = text.find(start)?;
+  let to = from + text[from..].find(end)? + end.len();
+  Some((&text[..from], &text[from..to], &text[to..]))
+}
+
+/// Inserts `line` among the lines starting with `prefix`, keeping them
+/// sorted. Fails if it is already there or there is no such line.
+fn insert_sorted(
+  text: &str,
+  prefix: &str,
+  line: &str,
+) -> Result<String, ScaffoldError> {
+  let mut lines: Vec<&str> = text.lines().collect();
+  if lines.contains(&line) {
+    return Err(ScaffoldError::Exists(line.to_string()));
+  }
+  let last = lines
+    .iter()
+    .rposition(|l| l.starts_with(prefix))
+    .ok_or_else(|| layout(&format!("no `{}` line", prefix)))?;
+  let at = lines
+    .iter()
+    .position(|l| l.starts_with(prefix) && *l > line)
+    .unwrap_or(last + 1);
+  lines.insert(at, line);
+  Ok(lines.join("\n") + "\n")
+}
+
+/// Paths and names of a day, relative to the workspace root.
+struct Paths {
+  year: Year,
+  day: Day,
+}
+
+impl Paths {
+  fn module(&self) -> String {
+    format!("d{:02}", self.day)
+  }
+
+  fn year_module(&self) -> String {
+    format!("_{}", self.year)
+  }
+
+  fn year_dir(&self) -> PathBuf {
+    Path::new("challenges/src").join(self.year_module())
+  }
+
+  fn example(&self) -> PathBuf {
+    Path::new("input")
+      .join(self.year.to_string())
+      .join(format!("{:02}_a.txt", self.day))
+  }
+
+  /// Expected answers for the example, checked by the examples test.
+  fn answers(&self) -> PathBuf {
+    self.example().with_extension("answers")
+  }
+
+  fn entry(&self) -> String {
+    let m = self.module();
+    format!(
+      "DaySolvers::new({}, &[{}::part_1, {}::part_2])",
+      self.day, m, m
+    )
+  }
+}
+
+const SOLVERS_START: &str = "pub const SOLVERS: YearSolvers = ";
+/// Start of a registry entry, `DaySolvers::new(...)` or another
+/// constructor.
+const DAY_ENTRY: &str = "DaySolvers::";
+
+fn solvers_block(year: Year, mut entries: Vec<(u8, String)>) -> String {
+  entries.sort();
+  let entries: Vec<String> = entries.into_iter().map(|(_, e)| e).collect();
+  format!(
+    "{}YearSolvers::new(\n  {},\n  {},\n);",
+    SOLVERS_START,
+    year,
+    slice_literal("  ", &entries, "  ")
+  )
+}
+
+/// Adds the day's module and its entry in the `SOLVERS` registry of an
+/// existing year module.
+fn register_day(mod_rs: &str, paths: &Paths) -> Result<String, ScaffoldError> {
+  let decl = format!("pub mod {};", paths.module());
+  let mod_rs = insert_sorted(mod_rs, "pub mod ", &decl)?;
+  let (before, block, after) = span(&mod_rs, SOLVERS_START, ");")
+    .ok_or_else(|| layout("no `SOLVERS` registry in the year module"))?;
+
+  let mut entries = Vec::new();
+  for (i, _) in block.match_indices(DAY_ENTRY) {
+    let (entry, args) = call(&block[i..])
+      .ok_or_else(|| layout("unreadable `DaySolvers` entry"))?;
+    let day = args
+      .split(',')
+      .next()
+      .and_then(|day| day.trim().parse::<u8>().ok())
+      .ok_or_else(|| layout("unreadable `DaySolvers` entry"))?;
+    if day == paths.day.get() {
+      return Err(ScaffoldError::Exists(entry.to_string()));
+    }
+    entries.push((day, entry.to_string()));
+  }
+  entries.push((paths.day.get(), paths.entry()));
+  Ok(format!(
+    "{}{}{}",
+    before,
+    solvers_block(paths.year, entries),
+    after
+  ))
+}
+
+fn new_year_module(paths: &Paths) -> String {
+  format!(
+    "use crate::challenge::{{DaySolvers, YearSolvers}};\n\npub mod {};\n\n{}\n",
+    paths.module(),
+    solvers_block(paths.year, vec![(paths.day.get(), paths.entry())])
+  )
+}
+
+/// Declares a new year module in lib.rs and lists it in `YEARS`.
+fn register_year(lib_rs: &str, paths: &Paths) -> Result<String, ScaffoldError> {
+  let decl = format!("mod {};", paths.year_module());
+  let lib_rs = insert_sorted(lib_rs, "mod _", &decl)?;
+  const START: &str = "pub const YEARS: &[YearSolvers] = ";
+  let (before, block, after) = span(&lib_rs, START, "];")
+    .ok_or_else(|| layout("no `YEARS` list in lib.rs"))?;
+  let list = block[START.len()..]
+    .trim_start_matches("&[")
+    .trim_end_matches("];");
+  let mut years: Vec<String> = list
+    .split(',')
+    .map(|year| year.trim().to_string())
+    .filter(|year| !year.is_empty())
+    .collect();
+  years.push(format!("{}::SOLVERS", paths.year_module()));
+  years.sort();
+  Ok(format!(
+    "{}{}{};{}",
+    before,
+    START,
+    slice_literal(START, &years, ""),
+    after
+  ))
+}
+
+fn create_new(path: &Path, contents: &str) -> Result<(), ScaffoldError> {
+  let mut file = std::fs::OpenOptions::new()
+    .write(true)
+    .create_new(true)
+    .open(path)
+    .map_err(|e| match e.kind() {
+      io::ErrorKind::AlreadyExists => {
+        ScaffoldError::Exists(path.display().to_string())
+      }
+      _ => ScaffoldError::Io(e),
+    })?;
+  file.write_all(contents.as_bytes())?;
+  Ok(())
+}
+
+/// Generates the module for `year`/`day` under the workspace at `root` and
+/// registers it with its year, setting up the year first if it is new.
+/// Everything is checked before the first write, so a refusal leaves the
+/// tree untouched.
+///
+/// Returns the created files, relative to `root`.
+pub fn new_day(
+  root: &Path,
+  year: Year,
+  day: Day,
+) -> Result<Vec<PathBuf>, ScaffoldError> {
+  let paths = Paths { year, day };
+  let module = paths.module();
+  let year_dir = paths.year_dir();
+  let mod_rs = year_dir.join("mod.rs");
+  let lib_rs = Path::new("challenges/src/lib.rs");
+  let source = year_dir.join(format!("{}.rs", module));
+
+  for existing in [&source, &year_dir.join(&module)] {
+    if root.join(existing).exists() {
+      return Err(ScaffoldError::Exists(existing.display().to_string()));
+    }
+  }
+  let read = |path: &Path| {
+    std::fs::read_to_string(root.join(path))
+      .map_err(|e| ScaffoldError::Layout(format!("{}: {}", path.display(), e)))
+  };
+  let new_year = !root.join(&mod_rs).exists();
+  let (new_mod_rs, new_lib_rs) = match new_year {
+    true => (
+      new_year_module(&paths),
+      Some(register_year(&read(lib_rs)?, &paths)?),
+    ),
+    false => (register_day(&read(&mod_rs)?, &paths)?, None),
+  };
+
+  let mut created = Vec::new();
+  if new_year {
+    std::fs::create_dir_all(root.join(&year_dir))?;
+    created.push(mod_rs.clone());
+  }
+  let example = paths.example().display().to_string().replace('\\', "/");
+  create_new(
+    &root.join(&source),
+    &TEMPLATE.replace("{example}", &example),
+  )?;
+  created.push(source);
+  std::fs::write(root.join(&mod_rs), new_mod_rs)?;
+  if let Some(new_lib_rs) = new_lib_rs {
+    std::fs::write(root.join(lib_rs), new_lib_rs)?;
+  }
+  for input in [paths.example(), paths.answers()] {
+    if !root.join(&input).exists() {
+      std::fs::create_dir_all(root.join(&input).parent().unwrap())?;
+      std::fs::write(root.join(&input), "")?;
+      created.push(input);
+    }
+  }
+  Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::mock::TempDir;
+
+  const LIB_RS: &str = "mod _2023;
+mod error;
+
+pub const YEARS: &[YearSolvers] = &[_2023::SOLVERS];
+";
+
+  const MOD_RS: &str =
+    "use crate::challenge::{DaySolvers, Solver, YearSolvers};
+
+pub mod d01;
+pub mod d11;
+pub mod d25;
+
+pub const SOLVERS: YearSolvers = YearSolvers::new(
+  2023,
+  &[
+    DaySolvers::new(1, &[d01::part_1, d01::part_2]),
+    DaySolvers::with_params(
+      11,
+      d11::PARAMS,
+      &[Solver::Plain(d11::part_1), Solver::WithParams(d11::part_2)],
+    ),
+    DaySolvers::new(25, &[d25::part_1]),
+  ],
+);
+";
+
+  fn workspace(name: &str) -> TempDir {
+    let root = TempDir::new(&format!("scaffold-{}", name));
+    std::fs::create_dir_all(root.join("challenges/src/_2023")).unwrap();
+    std::fs::write(root.join("challenges/src/lib.rs"), LIB_RS).unwrap();
+    std::fs::write(root.join("challenges/src/_2023/mod.rs"), MOD_RS).unwrap();
+    root
+  }
+
+  fn read(root: &Path, path: &str) -> String {
+    std::fs::read_to_string(root.join(path)).unwrap()
+  }
+
+  fn new(
+    root: &Path,
+    year: u16,
+    day: u8,
+  ) -> Result<Vec<PathBuf>, ScaffoldError> {
+    new_day(root, Year::new(year).unwrap(), Day::new(day).unwrap())
+  }
+
+  #[test]
+  fn wires_in_new_day() {
+    let root = workspace("new-day");
+    let created = new(&root, 2023, 12).unwrap();
+    assert_eq!(
+      created,
+      [
+        PathBuf::from("challenges/src/_2023/d12.rs"),
+        PathBuf::from("input/2023/12_a.txt"),
+        PathBuf::from("input/2023/12_a.answers"),
+      ]
+    );
+    assert_eq!(
+      read(&root, "challenges/src/_2023/mod.rs"),
+      MOD_RS
+        .replace("pub mod d25;", "pub mod d12;\npub mod d25;")
+        .replace(
+          "    DaySolvers::new(25",
+          "    DaySolvers::new(12, &[d12::part_1, d12::part_2]),\n    \
+         DaySolvers::new(25"
+        )
+    );
+    assert_eq!(read(&root, "challenges/src/lib.rs"), LIB_RS);
+    let source = read(&root, "challenges/src/_2023/d12.rs");
+    assert!(source.contains("\"../input/2023/12_a.txt\""));
+    assert!(source.contains("#[ignore"));
+    assert_eq!(read(&root, "input/2023/12_a.answers"), "");
+  }
+
+  #[test]
+  fn sets_up_new_year() {
+    let root = workspace("new-year");
+    let created = new(&root, 2022, 3).unwrap();
+    assert_eq!(created[0], PathBuf::from("challenges/src/_2022/mod.rs"));
+    assert_eq!(
+      read(&root, "challenges/src/lib.rs"),
+      "mod _2022;
+mod _2023;
+mod error;
+
+pub const YEARS: &[YearSolvers] = &[_2022::SOLVERS, _2023::SOLVERS];
+"
+    );
+    assert_eq!(
+      read(&root, "challenges/src/_2022/mod.rs"),
+      "use crate::challenge::{DaySolvers, YearSolvers};
+
+pub mod d03;
+
+pub const SOLVERS: YearSolvers = YearSolvers::new(
+  2022,
+  &[DaySolvers::new(3, &[d03::part_1, d03::part_2])],
+);
+"
+    );
+
+    new(&root, 2022, 1).unwrap();
+    assert!(read(&root, "challenges/src/_2022/mod.rs").contains(
+      "  &[
+    DaySolvers::new(1, &[d01::part_1, d01::part_2]),
+    DaySolvers::new(3, &[d03::part_1, d03::part_2]),
+  ],"
+    ));
+  }
+
+  #[test]
+  fn refuses_to_overwrite() {
+    let root = workspace("overwrite");
+    new(&root, 2023, 12).unwrap();
+    let mod_rs = read(&root, "challenges/src/_2023/mod.rs");
+    assert!(matches!(
+      new(&root, 2023, 12),
+      Err(ScaffoldError::Exists(_))
+    ));
+    assert!(matches!(new(&root, 2023, 1), Err(ScaffoldError::Exists(_))));
+    assert_eq!(read(&root, "challenges/src/_2023/mod.rs"), mod_rs);
+    assert!(!root.join("challenges/src/_2023/d01.rs").exists());
+  }
+}
@@ -0,0 +1,459 @@
+use std::{
+  fmt::Display,
+  io::{self, Write},
+  path::PathBuf,
+  str::FromStr,
+  time::{SystemTime, UNIX_EPOCH},
+};
+
+use challenges::Challenge;
+
+use crate::provider::{FetchError, HttpFetcher};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+  Correct,
+  TooHigh,
+  TooLow,
+  Wrong,
+  /// Submitted too soon after the previous attempt, the answer was not judged.
+  Throttled,
+  /// The part is already solved or not unlocked yet.
+  WrongLevel,
+  Unknown,
+}
+
+impl Verdict {
+  /// Reads the verdict out of the page the server replies with.
+  pub fn from_response(page: &str) -> Self {
+    if page.contains("That's the right answer") {
+      Verdict::Correct
+    } else if page.contains("answer is too high") {
+      Verdict::TooHigh
+    } else if page.contains("answer is too low") {
+      Verdict::TooLow
+    } else if page.contains("That's not the right answer") {
+      Verdict::Wrong
+    } else if page.contains("You gave an answer too recently") {
+      Verdict::Throttled
+    } else if page.contains("You don't seem to be solving the right level") {
+      Verdict::WrongLevel
+    } else {
+      Verdict::Unknown
+    }
+  }
+
+  /// Whether the verdict says anything about the answer itself.
+  pub fn is_judged(self) -> bool {
+    matches!(
+      self,
+      Verdict::Correct | Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong
+    )
+  }
+}
+
+const VERDICTS: [(Verdict, &str); 7] = [
+  (Verdict::Correct, "correct"),
+  (Verdict::TooHigh, "too-high"),
+  (Verdict::TooLow, "too-low"),
+  (Verdict::Wrong, "wrong"),
+  (Verdict::Throttled, "throttled"),
+  (Verdict::WrongLevel, "wrong-level"),
+  (Verdict::Unknown, "unknown"),
+];
+
+impl Display for Verdict {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let (_, name) = VERDICTS.iter().find(|(v, _)| v == self).unwrap();
+    write!(f, "{}", name)
+  }
+}
+
+impl FromStr for Verdict {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    VERDICTS
+      .iter()
+      .find(|(_, name)| *name == s)
+      .map(|&(v, _)| v)
+      .ok_or_else(|| format!("Unknown verdict {:?}", s))
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attempt {
+  pub answer: i64,
+  /// Seconds since the Unix epoch.
+  pub time: u64,
+  pub verdict: Verdict,
+}
+
+impl Display for Attempt {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}\t{}\t{}", self.time, self.answer, self.verdict)
+  }
+}
+
+impl FromStr for Attempt {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let fields: Vec<&str> = s.split('\t').collect();
+    let [time, answer, verdict] = fields[..] else {
+      return Err(format!("Expected 3 tab separated fields in {:?}", s));
+    };
+    Ok(Attempt {
+      answer: answer.parse().map_err(|e| format!("{}: {:?}", e, answer))?,
+      time: time.parse().map_err(|e| format!("{}: {:?}", e, time))?,
+      verdict: verdict.parse()?,
+    })
+  }
+}
+
+/// Why an answer is not worth sending.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Refusal {
+  Solved { answer: i64 },
+  KnownWrong { verdict: Verdict },
+  AboveTooHigh { bound: i64 },
+  BelowTooLow { bound: i64 },
+}
+
+impl Display for Refusal {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Refusal::Solved { answer } => {
+        write!(f, "Already solved, the answer was {}", answer)
+      }
+      Refusal::KnownWrong { verdict } => {
+        write!(f, "Already submitted, the verdict was {}", verdict)
+      }
+      Refusal::AboveTooHigh { bound } => {
+        write!(f, "Not below {}, which was too high", bound)
+      }
+      Refusal::BelowTooLow { bound } => {
+        write!(f, "Not above {}, which was too low", bound)
+      }
+    }
+  }
+}
+
+/// Every attempt at one puzzle part, oldest first.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct History {
+  pub attempts: Vec<Attempt>,
+}
+
+impl History {
+  pub fn solution(&self) -> Option<i64> {
+    self
+      .attempts
+      .iter()
+      .find(|a| a.verdict == Verdict::Correct)
+      .map(|a| a.answer)
+  }
+
+  /// Smallest answer known to be too high.
+  pub fn upper_bound(&self) -> Option<i64> {
+    self.bound(Verdict::TooHigh).min()
+  }
+
+  /// Largest answer known to be too low.
+  pub fn lower_bound(&self) -> Option<i64> {
+    self.bound(Verdict::TooLow).max()
+  }
+
+  fn bound(&self, verdict: Verdict) -> impl Iterator<Item = i64> + '_ {
+    self
+      .attempts
+      .iter()
+      .filter(move |a| a.verdict == verdict)
+      .map(|a| a.answer)
+  }
+
+  pub fn check(&self, answer: i64) -> Result<(), Refusal> {
+    if let Some(solution) = self.solution() {
+      return Err(Refusal::Solved { answer: solution });
+    }
+    let judged = self
+      .attempts
+      .iter()
+      .find(|a| a.answer == answer && a.verdict.is_judged());
+    if let Some(attempt) = judged {
+      return Err(Refusal::KnownWrong {
+        verdict: attempt.verdict,
+      });
+    }
+    if let Some(bound) = self.upper_bound().filter(|&b| answer >= b) {
+      return Err(Refusal::AboveTooHigh { bound });
+    }
+    if let Some(bound) = self.lower_bound().filter(|&b| answer <= b) {
+      return Err(Refusal::BelowTooLow { bound });
+    }
+    Ok(())
+  }
+}
+
+/// Attempts stored as `<dir>/<year>/<day>_<part>.attempts`, one tab separated
+/// `time answer verdict` line each.
+#[derive(Debug, Clone)]
+pub struct AttemptLog {
+  dir: PathBuf,
+}
+
+impl AttemptLog {
+  pub fn new(dir: impl Into<PathBuf>) -> Self {
+    AttemptLog { dir: dir.into() }
+  }
+
+  pub fn path(&self, challenge: Challenge) -> PathBuf {
+    let Challenge { year, day, part } = challenge;
+    self
+      .dir
+      .join(year.to_string())
+      .join(format!("{:02}_{}.attempts", day, part))
+  }
+
+  pub fn history(&self, challenge: Challenge) -> io::Result<History> {
+    let path = self.path(challenge);
+    let contents = match std::fs::read_to_string(&path) {
+      Ok(contents) => contents,
+      Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+      Err(e) => return Err(e),
+    };
+    let attempts = contents
+      .lines()
+      .enumerate()
+      .filter(|(_, line)| !line.trim().is_empty())
+      .map(|(i, line)| {
+        line.parse().map_err(|e| {
+          let at = format!("{}:{}: {}", path.display(), i + 1, e);
+          io::Error::new(io::ErrorKind::InvalidData, at)
+        })
+      })
+      .collect::<io::Result<_>>()?;
+    Ok(History { attempts })
+  }
+
+  pub fn record(
+    &self,
+    challenge: Challenge,
+    attempt: &Attempt,
+  ) -> io::Result<()> {
+    let path = self.path(challenge);
+    std::fs::create_dir_all(path.parent().unwrap())?;
+    let mut file = std::fs::OpenOptions::new()
+      .create(true)
+      .append(true)
+      .open(path)?;
+    writeln!(file, "{}", attempt)
+  }
+}
+
+/// Destination of answers, judging them.
+pub trait Submitter {
+  fn submit(
+    &mut self,
+    challenge: Challenge,
+    answer: i64,
+  ) -> Result<Verdict, FetchError>;
+}
+
+impl Submitter for HttpFetcher {
+  fn submit(
+    &mut self,
+    challenge: Challenge,
+    answer: i64,
+  ) -> Result<Verdict, FetchError> {
+    let Challenge { year, day, part } = challenge;
+    let page = self.post(
+      &format!("/{}/day/{}/answer", year, day),
+      &[
+        ("level", &part.to_string()),
+        ("answer", &answer.to_string()),
+      ],
+    )?;
+    Ok(Verdict::from_response(&page))
+  }
+}
+
+#[derive(Debug)]
+pub enum SubmitError {
+  Refused(Refusal),
+  Io(io::Error),
+  Fetch(FetchError),
+}
+
+impl Display for SubmitError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      SubmitError::Refused(r) => write!(f, "Not submitting: {}", r),
+      SubmitError::Io(e) => write!(f, "{}", e),
+      SubmitError::Fetch(e) => write!(f, "{}", e),
+    }
+  }
+}
+
+impl std::error::Error for SubmitError {}
+
+impl From<io::Error> for SubmitError {
+  fn from(e: io::Error) -> Self {
+    SubmitError::Io(e)
+  }
+}
+
+impl From<FetchError> for SubmitError {
+  fn from(e: FetchError) -> Self {
+    SubmitError::Fetch(e)
+  }
+}
+
+/// Sends `answer` unless the log already rules it out, then logs the verdict.
+pub fn submit(
+  log: &AttemptLog,
+  submitter: &mut impl Submitter,
+  challenge: Challenge,
+  answer: i64,
+) -> Result<Verdict, SubmitError> {
+  log
+    .history(challenge)?
+    .check(answer)
+    .map_err(SubmitError::Refused)?;
+  let verdict = submitter.submit(challenge, answer)?;
+  let time = SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map_or(0, |d| d.as_secs());
+  log.record(
+    challenge,
+    &Attempt {
+      answer,
+      time,
+      verdict,
+    },
+  )?;
+  Ok(verdict)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    mock::{MockServer, TempDir},
+    provider::RateLimiter,
+  };
+  use std::time::Duration;
+
+  fn attempt(answer: i64, verdict: Verdict) -> Attempt {
+    Attempt {
+      answer,
+      time: 0,
+      verdict,
+    }
+  }
+
+  #[test]
+  fn bounds_from_verdicts() {
+    let history = History {
+      attempts: vec![
+        attempt(100, Verdict::TooHigh),
+        attempt(10, Verdict::TooLow),
+        attempt(80, Verdict::TooHigh),
+        attempt(20, Verdict::TooLow),
+        attempt(50, Verdict::Wrong),
+        attempt(60, Verdict::Throttled),
+      ],
+    };
+    assert_eq!(history.upper_bound(), Some(80));
+    assert_eq!(history.lower_bound(), Some(20));
+    assert_eq!(history.check(90), Err(Refusal::AboveTooHigh { bound: 80 }));
+    assert_eq!(history.check(81), Err(Refusal::AboveTooHigh { bound: 80 }));
+    assert_eq!(history.check(-3), Err(Refusal::BelowTooLow { bound: 20 }));
+    assert_eq!(
+      history.check(50),
+      Err(Refusal::KnownWrong {
+        verdict: Verdict::Wrong
+      })
+    );
+    assert_eq!(history.check(60), Ok(()));
+    assert_eq!(history.check(21), Ok(()));
+  }
+
+  #[test]
+  fn nothing_after_solved() {
+    let history = History {
+      attempts: vec![attempt(7, Verdict::Wrong), attempt(42, Verdict::Correct)],
+    };
+    assert_eq!(history.check(43), Err(Refusal::Solved { answer: 42 }));
+  }
+
+  #[test]
+  fn attempt_round_trip() {
+    for (verdict, _) in VERDICTS {
+      let a = Attempt {
+        answer: -12,
+        time: 1_700_000_000,
+        verdict,
+      };
+      assert_eq!(a.to_string().parse(), Ok(a));
+    }
+    assert!("1\t2".parse::<Attempt>().is_err());
+  }
+
+  #[test]
+  fn submits_and_records() {
+    let server = MockServer::start(|request| {
+      let page = match request.body.as_str() {
+        "level=1&answer=1000" => {
+          "That's not the right answer; your answer is too high."
+        }
+        "level=1&answer=5" => {
+          "That's not the right answer; your answer is too low."
+        }
+        "level=1&answer=114" => "That's the right answer!",
+        _ => "That's not the right answer.",
+      };
+      (200, format!("<article><p>{}</p></article>", page))
+    });
+    let dir = TempDir::new("submits-and-records");
+    let log = AttemptLog::new(dir.path());
+    let mut client = HttpFetcher::new(
+      &server.url(),
+      "secret",
+      RateLimiter::new(Duration::ZERO),
+    );
+    let challenge = "2023/9/1".parse().unwrap();
+    let mut send = |answer| submit(&log, &mut client, challenge, answer);
+
+    assert_eq!(send(1000).unwrap(), Verdict::TooHigh);
+    assert_eq!(send(5).unwrap(), Verdict::TooLow);
+    assert!(matches!(send(2000), Err(SubmitError::Refused(_))));
+    assert!(matches!(send(5), Err(SubmitError::Refused(_))));
+    assert_eq!(send(113).unwrap(), Verdict::Wrong);
+    assert!(matches!(send(113), Err(SubmitError::Refused(_))));
+    assert_eq!(send(114).unwrap(), Verdict::Correct);
+    assert!(matches!(send(115), Err(SubmitError::Refused(_))));
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 4);
+    assert!(requests.iter().all(|r| r.method == "POST"));
+    assert_eq!(requests[0].path, "/2023/day/9/answer");
+    assert_eq!(requests[0].header("cookie"), Some("session=secret"));
+
+    let history = log.history(challenge).unwrap();
+    let answers: Vec<_> = history
+      .attempts
+      .iter()
+      .map(|a| (a.answer, a.verdict))
+      .collect();
+    assert_eq!(
+      answers,
+      [
+        (1000, Verdict::TooHigh),
+        (5, Verdict::TooLow),
+        (113, Verdict::Wrong),
+        (114, Verdict::Correct)
+      ]
+    );
+  }
+}